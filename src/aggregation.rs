@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::models::{Duration, Metrics, MetricsRecord, Status};
+
+/// Which records take part in an aggregation and how they are bucketed.
+#[derive(Debug, Clone, Default)]
+pub struct AggregationOptions {
+    /// Wall-clock window, aligned to the Unix epoch. `None` puts all records of a duration
+    /// into one bucket.
+    pub window: Option<chrono::Duration>,
+    /// Include records ONTAP filled in after a gap (`backfilled_data`).
+    pub include_backfilled: bool,
+    /// Include records of the `partial_*` statuses.
+    pub include_partial: bool,
+}
+
+impl AggregationOptions {
+    fn accepts(&self, status: &Status) -> bool {
+        match status {
+            Status::Ok => true,
+            Status::BackfilledData => self.include_backfilled,
+            Status::PartialNoData
+            | Status::PartialNoUuid
+            | Status::PartialNoResponse
+            | Status::PartialOtherError => self.include_partial,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Summary {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Summary {
+    /// Nearest-rank percentiles over `values`.
+    pub fn of(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let rank = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1];
+        Self {
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p95: rank(0.95),
+            p99: rank(0.99),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MetricsSummary {
+    pub read: Summary,
+    pub write: Summary,
+    pub other: Summary,
+    pub total: Summary,
+}
+
+impl MetricsSummary {
+    fn of<'a>(metrics: impl Iterator<Item = &'a Metrics> + Clone) -> Self {
+        let values =
+            |f: fn(&Metrics) -> u128| metrics.clone().map(|m| f(m) as f64).collect::<Vec<_>>();
+        Self {
+            read: Summary::of(&values(|m| m.read)),
+            write: Summary::of(&values(|m| m.write)),
+            other: Summary::of(&values(|m| m.other)),
+            total: Summary::of(&values(|m| m.total)),
+        }
+    }
+}
+
+/// Latency averaged over a bucket, each sample weighted by its IOPS.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WeightedLatency {
+    pub read: f64,
+    pub write: f64,
+    pub other: f64,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsBucket {
+    pub duration: Duration,
+    /// Start of the window, `None` when aggregating without a window.
+    pub start: Option<DateTime<Utc>>,
+    pub count: usize,
+    pub iops: MetricsSummary,
+    pub throughput: MetricsSummary,
+    /// `None` when no record of the bucket carried latency.
+    pub latency: Option<WeightedLatency>,
+}
+
+/// Groups `records` by duration and window, independent of their order, and summarizes each
+/// group. Buckets are returned ordered by duration and window start.
+pub fn aggregate(records: &[MetricsRecord], options: &AggregationOptions) -> Vec<MetricsBucket> {
+    let mut buckets: BTreeMap<(Duration, Option<i64>), Vec<&MetricsRecord>> = BTreeMap::new();
+    for record in records.iter().filter(|r| options.accepts(&r.status)) {
+        let start = options
+            .window
+            .map(|w| window_start(record.timestamp, w.num_seconds().max(1)));
+        buckets
+            .entry((record.duration, start))
+            .or_default()
+            .push(record);
+    }
+    buckets
+        .into_iter()
+        .map(|((duration, start), records)| MetricsBucket {
+            duration,
+            start: start.and_then(|s| Utc.timestamp_opt(s, 0).single()),
+            count: records.len(),
+            iops: MetricsSummary::of(records.iter().map(|r| &r.iops)),
+            throughput: MetricsSummary::of(records.iter().map(|r| &r.throughput)),
+            latency: weighted_latency(&records),
+        })
+        .collect()
+}
+
+fn window_start(timestamp: DateTime<Utc>, window: i64) -> i64 {
+    timestamp.timestamp().div_euclid(window) * window
+}
+
+fn weighted_latency(records: &[&MetricsRecord]) -> Option<WeightedLatency> {
+    let with_latency = records
+        .iter()
+        .filter_map(|r| Some((&r.iops, r.latency.as_ref()?)))
+        .collect::<Vec<_>>();
+    if with_latency.is_empty() {
+        return None;
+    }
+    let weighted = |f: fn(&Metrics) -> u128| {
+        let (sum, weight) =
+            with_latency
+                .iter()
+                .fold((0.0, 0.0), |(sum, weight), (iops, latency)| {
+                    let w = f(iops) as f64;
+                    (sum + f(latency) as f64 * w, weight + w)
+                });
+        if weight > 0.0 {
+            sum / weight
+        } else {
+            0.0
+        }
+    };
+    Some(WeightedLatency {
+        read: weighted(|m| m.read),
+        write: weighted(|m| m.write),
+        other: weighted(|m| m.other),
+        total: weighted(|m| m.total),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::{aggregate, AggregationOptions};
+    use crate::models::{Duration, Metrics, MetricsRecord, Status};
+
+    fn record(offset: i64, iops: u128, latency: u128, status: Status) -> MetricsRecord {
+        let m = |v: u128| Metrics {
+            read: v,
+            write: 0,
+            other: 0,
+            total: v,
+        };
+        MetricsRecord {
+            duration: Duration::PT15S,
+            iops: m(iops),
+            latency: Some(m(latency)),
+            throughput: m(iops * 4096),
+            processor_utilization: None,
+            status,
+            timestamp: Utc.timestamp_opt(1_699_999_200 + offset, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn unordered_windows() {
+        let records = vec![
+            record(3600, 100, 10, Status::Ok),
+            record(0, 100, 100, Status::Ok),
+            record(3615, 300, 20, Status::Ok),
+            record(15, 300, 200, Status::BackfilledData),
+            record(30, 1000, 1000, Status::PartialNoData),
+            record(45, 1000, 1000, Status::Error),
+        ];
+        let options = AggregationOptions {
+            window: Some(chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+
+        let buckets = aggregate(&records, &options);
+        assert_eq!(2, buckets.len());
+        assert_eq!(1, buckets[0].count);
+        assert_eq!(
+            Utc.timestamp_opt(1_699_999_200, 0).unwrap(),
+            buckets[0].start.unwrap()
+        );
+        let second = &buckets[1];
+        assert_eq!(2, second.count);
+        assert_eq!(100.0, second.iops.total.min);
+        assert_eq!(300.0, second.iops.total.max);
+        assert_eq!(200.0, second.iops.total.mean);
+        assert_eq!(300.0, second.iops.total.p99);
+        assert_eq!(17.5, second.latency.unwrap().total);
+
+        let options = AggregationOptions {
+            include_backfilled: true,
+            include_partial: true,
+            ..options
+        };
+        assert_eq!(3, aggregate(&records, &options)[0].count);
+        assert_eq!(
+            5,
+            aggregate(
+                &records,
+                &AggregationOptions {
+                    window: None,
+                    ..options
+                }
+            )[0]
+            .count
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use color_eyre::eyre::{eyre, Result};
+use rontap::exporter::{serve, ExporterConfig};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    color_eyre::install()?;
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let path = std::env::args()
+        .nth(1)
+        .ok_or_else(|| eyre!("usage: rontap-exporter <config.toml>"))?;
+    let config = ExporterConfig::from_toml(&std::fs::read_to_string(path)?)?;
+    serve(config).await?;
+    Ok(())
+}
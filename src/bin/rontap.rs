@@ -0,0 +1,630 @@
+use std::{collections::BTreeMap, fmt::Debug, path::PathBuf};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use color_eyre::eyre::{bail, eyre, Result};
+use reqwest::Url;
+use rontap::{
+    models::{ip_interface::IpInterface, job::JobResponse, volume::Volume},
+    OntapClient, OntapConnectionParams,
+};
+use serde::Deserialize;
+
+/// Command line access to NetApp ONTAP clusters.
+#[derive(Parser)]
+#[command(name = "rontap")]
+struct Cli {
+    /// Config file with the named clusters, defaults to `~/.config/rontap/config.toml`.
+    #[arg(long, env = "RONTAP_CONFIG")]
+    config: Option<PathBuf>,
+    /// Cluster from the config file, defaults to its `default` entry.
+    #[arg(long, short, env = "RONTAP_CLUSTER")]
+    cluster: Option<String>,
+    #[arg(long, short, value_enum, default_value_t = Output::Table)]
+    output: Output,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List, show, resize and move volumes
+    #[command(subcommand)]
+    Volume(VolumeCommand),
+    /// List snapshots of a volume
+    #[command(subcommand)]
+    Snapshot(SnapshotCommand),
+    /// List, revert and disable network interfaces
+    #[command(subcommand)]
+    Lif(LifCommand),
+    /// List disks
+    #[command(subcommand)]
+    Disk(DiskCommand),
+    /// Show shelves and their bays
+    #[command(subcommand)]
+    Shelf(ShelfCommand),
+    /// List SnapMirror relationships
+    #[command(subcommand)]
+    Snapmirror(SnapmirrorCommand),
+    /// List jobs
+    #[command(subcommand)]
+    Job(JobCommand),
+}
+
+#[derive(Subcommand)]
+enum VolumeCommand {
+    List,
+    Show {
+        name: String,
+        #[arg(long)]
+        svm: Option<String>,
+    },
+    /// Set the volume size, e.g. `500G` or `2T`
+    Resize {
+        name: String,
+        size: String,
+        #[arg(long)]
+        svm: Option<String>,
+    },
+    /// Move the volume to another aggregate
+    Move {
+        name: String,
+        aggregate: String,
+        #[arg(long)]
+        svm: Option<String>,
+        /// Seconds the cutover may take.
+        #[arg(long, default_value_t = 30)]
+        cutover_window: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotCommand {
+    List {
+        volume: String,
+        #[arg(long)]
+        svm: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum LifCommand {
+    List,
+    /// Revert the LIF to its home port
+    Revert {
+        name: String,
+    },
+    Disable {
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DiskCommand {
+    List,
+}
+
+#[derive(Subcommand)]
+enum ShelfCommand {
+    Show { name: Option<String> },
+}
+
+#[derive(Subcommand)]
+enum SnapmirrorCommand {
+    List,
+}
+
+#[derive(Subcommand)]
+enum JobCommand {
+    List,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    default: Option<String>,
+    clusters: BTreeMap<String, ClusterConfig>,
+}
+
+#[derive(Deserialize)]
+struct ClusterConfig {
+    url: String,
+    username: String,
+    /// Falls back to the `RONTAP_PASSWORD` environment variable.
+    password: Option<String>,
+}
+
+impl Config {
+    fn load(path: Option<PathBuf>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => PathBuf::from(std::env::var("HOME")?).join(".config/rontap/config.toml"),
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| eyre!("cannot read config {}: {e}", path.display()))?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    fn cluster(self, name: Option<String>) -> Result<ClusterConfig> {
+        let name = name
+            .or(self.default)
+            .ok_or_else(|| eyre!("no cluster given and no default configured"))?;
+        let mut clusters = self.clusters;
+        clusters
+            .remove(&name)
+            .ok_or_else(|| eyre!("cluster {name} not found in config"))
+    }
+}
+
+/// Rows with fixed columns, rendered as aligned table, CSV or JSON objects.
+struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: vec![],
+        }
+    }
+
+    fn row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    fn render(&self, output: Output) -> String {
+        match output {
+            Output::Table => {
+                let widths = self
+                    .headers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, h)| {
+                        self.rows
+                            .iter()
+                            .map(|r| r[i].chars().count())
+                            .chain([h.len()])
+                            .max()
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<_>>();
+                let line = |cells: Vec<&str>| {
+                    let line = cells
+                        .iter()
+                        .zip(&widths)
+                        .map(|(c, w)| format!("{c:w$}"))
+                        .collect::<Vec<_>>()
+                        .join("  ");
+                    format!("{}\n", line.trim_end())
+                };
+                let headers = self
+                    .headers
+                    .iter()
+                    .map(|h| h.to_uppercase())
+                    .collect::<Vec<_>>();
+                let mut out = line(headers.iter().map(String::as_str).collect());
+                for row in &self.rows {
+                    out.push_str(&line(row.iter().map(String::as_str).collect()));
+                }
+                out
+            }
+            Output::Csv => {
+                let line = |cells: Vec<&str>| {
+                    let line = cells.into_iter().map(csv_escape).collect::<Vec<_>>();
+                    format!("{}\n", line.join(","))
+                };
+                let mut out = line(self.headers.clone());
+                for row in &self.rows {
+                    out.push_str(&line(row.iter().map(String::as_str).collect()));
+                }
+                out
+            }
+            Output::Json => {
+                let objects = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        self.headers
+                            .iter()
+                            .zip(row)
+                            .map(|(h, v)| (h.to_string(), serde_json::Value::from(v.as_str())))
+                            .collect::<serde_json::Map<_, _>>()
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}\n", serde_json::Value::from(objects))
+            }
+        }
+    }
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// snake_case name of a unit enum variant.
+fn name<T: Debug>(value: &T) -> String {
+    let mut out = String::new();
+    for (i, c) in format!("{value:?}").chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// `, job <uuid>` for the job started by an asynchronous request, to follow it with `job list`.
+fn job_suffix(job: &JobResponse) -> String {
+    job.job
+        .as_ref()
+        .map(|j| format!(", job {}", j.uuid))
+        .unwrap_or_default()
+}
+
+fn opt<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Parses a size in bytes with an optional binary `K`, `M`, `G`, `T` or `P` suffix.
+fn parse_size(size: &str) -> Result<u64> {
+    let size = size.trim().to_uppercase();
+    let size = size.trim_end_matches('B').trim_end_matches('I');
+    let (number, shift) = match size.chars().last() {
+        Some('K') => (&size[..size.len() - 1], 10),
+        Some('M') => (&size[..size.len() - 1], 20),
+        Some('G') => (&size[..size.len() - 1], 30),
+        Some('T') => (&size[..size.len() - 1], 40),
+        Some('P') => (&size[..size.len() - 1], 50),
+        _ => (size, 0),
+    };
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| eyre!("invalid size {size}"))?;
+    if number < 0.0 {
+        bail!("invalid size {size}");
+    }
+    Ok((number * (1u64 << shift) as f64) as u64)
+}
+
+async fn find_volume(client: &OntapClient, name: &str, svm: Option<&str>) -> Result<Volume> {
+    let mut matches = client
+        .get_volumes()
+        .await?
+        .records
+        .into_iter()
+        .filter(|v| v.name == name && (svm.is_none() || svm == Some(v.svm.name.as_str())))
+        .collect::<Vec<_>>();
+    match matches.len() {
+        0 => bail!("volume {name} not found"),
+        1 => Ok(matches.remove(0)),
+        _ => bail!("volume {name} exists in several SVMs, select one with --svm"),
+    }
+}
+
+async fn find_lif(client: &OntapClient, name: &str) -> Result<IpInterface> {
+    client
+        .get_ip_interfaces()
+        .await?
+        .records
+        .into_iter()
+        .find(|l| l.name == name)
+        .ok_or_else(|| eyre!("LIF {name} not found"))
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let cluster = Config::load(cli.config)?.cluster(cli.cluster)?;
+    let password = match cluster.password {
+        Some(password) => password,
+        None => std::env::var("RONTAP_PASSWORD")
+            .map_err(|_| eyre!("no password configured and RONTAP_PASSWORD not set"))?,
+    };
+    let client = OntapConnectionParams {
+        url: Url::parse(&cluster.url)?,
+        username: cluster.username,
+        password,
+    }
+    .connect()
+    .await?;
+    let output = cli.output;
+
+    let table = match cli.command {
+        Command::Volume(VolumeCommand::List) => {
+            let mut table = Table::new(&["svm", "name", "size", "used", "available", "uuid"]);
+            for v in client.get_volumes().await?.records {
+                table.row(vec![
+                    v.svm.name.clone(),
+                    v.name.clone(),
+                    v.size.to_string(),
+                    v.space.used.to_string(),
+                    v.space.available.to_string(),
+                    v.uuid.clone(),
+                ]);
+            }
+            table
+        }
+        Command::Volume(VolumeCommand::Show { name: volume, svm }) => {
+            let v = find_volume(&client, &volume, svm.as_deref()).await?;
+            if output == Output::Json {
+                println!("{}", serde_json::to_string_pretty(&v)?);
+                return Ok(());
+            }
+            let mut table = Table::new(&["field", "value"]);
+            let aggregates = v
+                .aggregates
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>();
+            for (field, value) in [
+                ("name", v.name.clone()),
+                ("uuid", v.uuid.clone()),
+                ("svm", v.svm.name.clone()),
+                ("aggregates", aggregates.join(" ")),
+                ("style", opt(v.style.as_ref().map(name))),
+                ("size", v.size.to_string()),
+                ("used", v.space.used.to_string()),
+                ("available", v.space.available.to_string()),
+                ("percent_used", v.percent_used().to_string()),
+                ("files_used", v.files.used.to_string()),
+                ("files_maximum", v.files.maximum.to_string()),
+                ("autosize_mode", v.autosize.mode.clone()),
+                ("encrypted", v.encryption.enabled.to_string()),
+                ("flexclone", v.clone.is_flexclone.to_string()),
+                (
+                    "qos_policy",
+                    opt(v.qos.as_ref().map(|q| q.policy.name.clone())),
+                ),
+                ("movement", opt(v.movement.as_ref().map(|m| name(&m.state)))),
+            ] {
+                table.row(vec![field.to_string(), value]);
+            }
+            table
+        }
+        Command::Volume(VolumeCommand::Resize {
+            name: volume,
+            size,
+            svm,
+        }) => {
+            let v = find_volume(&client, &volume, svm.as_deref()).await?;
+            let size = parse_size(&size)?;
+            let job = client.resize_volume_job(&v.uuid, size).await?;
+            println!(
+                "Resizing volume {} of SVM {} to {size} bytes{}",
+                v.name,
+                v.svm.name,
+                job_suffix(&job)
+            );
+            return Ok(());
+        }
+        Command::Volume(VolumeCommand::Move {
+            name: volume,
+            aggregate,
+            svm,
+            cutover_window,
+        }) => {
+            let v = find_volume(&client, &volume, svm.as_deref()).await?;
+            let aggregate = client
+                .get_aggregates()
+                .await?
+                .records
+                .into_iter()
+                .find(|a| a.name == aggregate)
+                .ok_or_else(|| eyre!("aggregate {aggregate} not found"))?;
+            let job = client
+                .move_volume_job(&v.uuid, &aggregate.uuid, cutover_window)
+                .await?;
+            println!(
+                "Moving volume {} of SVM {} to aggregate {}{}",
+                v.name,
+                v.svm.name,
+                aggregate.name,
+                job_suffix(&job)
+            );
+            return Ok(());
+        }
+        Command::Snapshot(SnapshotCommand::List { volume, svm }) => {
+            let v = find_volume(&client, &volume, svm.as_deref()).await?;
+            let mut table = Table::new(&["name", "size", "state", "owners", "uuid"]);
+            for s in client.get_volume_snapshots(&v.uuid).await?.records {
+                let owners = s.owners.iter().flatten().map(name).collect::<Vec<_>>();
+                table.row(vec![
+                    s.name,
+                    opt(s.size),
+                    opt(s.state.as_ref().map(name)),
+                    owners.join(" "),
+                    s.uuid,
+                ]);
+            }
+            table
+        }
+        Command::Lif(LifCommand::List) => {
+            let mut table = Table::new(&[
+                "svm", "name", "address", "state", "enabled", "node", "port", "is_home",
+            ]);
+            for l in client.get_ip_interfaces().await?.records {
+                table.row(vec![
+                    opt(l.svm.map(|s| s.name)),
+                    l.name,
+                    format!("{}/{}", l.ip.address, l.ip.netmask),
+                    name(&l.state),
+                    l.enabled.to_string(),
+                    l.location.node.name,
+                    l.location.port.name,
+                    l.location.is_home.to_string(),
+                ]);
+            }
+            table
+        }
+        Command::Lif(LifCommand::Revert { name: lif }) => {
+            let lif = find_lif(&client, &lif).await?;
+            client.ip_interface_revert(&lif.uuid).await?;
+            println!("Reverted LIF {} to its home port", lif.name);
+            return Ok(());
+        }
+        Command::Lif(LifCommand::Disable { name: lif }) => {
+            let lif = find_lif(&client, &lif).await?;
+            client
+                .ip_interface_set_admin_status(&lif.uuid, false)
+                .await?;
+            println!("Disabled LIF {}", lif.name);
+            return Ok(());
+        }
+        Command::Disk(DiskCommand::List) => {
+            let mut table = Table::new(&[
+                "name",
+                "node",
+                "type",
+                "class",
+                "container",
+                "state",
+                "size",
+                "model",
+                "serial",
+            ]);
+            for d in client.get_disks().await?.records {
+                let node = d.node_name().unwrap_or_default().to_string();
+                table.row(vec![
+                    d.name,
+                    node,
+                    name(&d.typ),
+                    name(&d.class),
+                    name(&d.container_type),
+                    name(&d.state),
+                    d.usable_size.to_string(),
+                    d.model,
+                    d.serial_number,
+                ]);
+            }
+            table
+        }
+        Command::Shelf(ShelfCommand::Show { name: shelf }) => {
+            let shelves = client
+                .get_shelves()
+                .await?
+                .records
+                .into_iter()
+                .filter(|s| shelf.is_none() || shelf.as_deref() == Some(s.name.as_str()))
+                .collect::<Vec<_>>();
+            if shelf.is_some() && shelves.is_empty() {
+                bail!("shelf not found");
+            }
+            match output {
+                Output::Table => {
+                    for s in shelves {
+                        println!("{s}");
+                    }
+                    return Ok(());
+                }
+                Output::Json => {
+                    println!("{}", serde_json::to_string_pretty(&shelves)?);
+                    return Ok(());
+                }
+                Output::Csv => {
+                    let mut table = Table::new(&["name", "model", "state", "disk_count", "uid"]);
+                    for s in shelves {
+                        table.row(vec![
+                            s.name,
+                            s.model,
+                            name(&s.state),
+                            s.disk_count.to_string(),
+                            s.uid,
+                        ]);
+                    }
+                    table
+                }
+            }
+        }
+        Command::Snapmirror(SnapmirrorCommand::List) => {
+            let mut table = Table::new(&[
+                "source",
+                "destination",
+                "state",
+                "healthy",
+                "transfer",
+                "uuid",
+            ]);
+            for r in client.get_snapmirror_relationships().await?.records {
+                table.row(vec![
+                    r.source.path,
+                    r.destination.path,
+                    name(&r.state),
+                    r.healthy.to_string(),
+                    opt(r.transfer.as_ref().map(|t| name(&t.state))),
+                    r.uuid,
+                ]);
+            }
+            table
+        }
+        Command::Job(JobCommand::List) => {
+            let mut table = Table::new(&["uuid", "state", "description", "message", "start_time"]);
+            for j in client.get_jobs().await?.records {
+                table.row(vec![
+                    j.uuid,
+                    name(&j.state),
+                    opt(j.description),
+                    opt(j.message),
+                    opt(j.start_time.map(|t| t.to_rfc3339())),
+                ]);
+            }
+            table
+        }
+    };
+    print!("{}", table.render(output));
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    color_eyre::install()?;
+    run(Cli::parse()).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::{name, parse_size, Output, Table};
+
+    #[derive(Debug)]
+    enum State {
+        TakenOver,
+    }
+
+    #[test]
+    fn render_outputs() {
+        let mut table = Table::new(&["name", "state"]);
+        table.row(vec!["vol1".into(), name(&State::TakenOver)]);
+        table.row(vec!["vol,\"2\"".into(), "ok".into()]);
+
+        assert_eq!(
+            "NAME     STATE\nvol1     taken_over\nvol,\"2\"  ok\n",
+            table.render(Output::Table)
+        );
+        assert_eq!(
+            "name,state\nvol1,taken_over\n\"vol,\"\"2\"\"\",ok\n",
+            table.render(Output::Csv)
+        );
+        assert_eq!(
+            "[{\"name\":\"vol1\",\"state\":\"taken_over\"},{\"name\":\"vol,\\\"2\\\"\",\"state\":\"ok\"}]\n",
+            table.render(Output::Json)
+        );
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(1024, parse_size("1k").unwrap());
+        assert_eq!(500 << 30, parse_size("500G").unwrap());
+        assert_eq!(3 << 39, parse_size("1.5TiB").unwrap());
+        assert_eq!(4096, parse_size("4096").unwrap());
+        assert!(parse_size("lots").is_err());
+    }
+}
@@ -0,0 +1,807 @@
+//! OpenMetrics exporter used by the `rontap-exporter` binary.
+//!
+//! ```toml
+//! listen = "0.0.0.0:9900"
+//! scrape_timeout_secs = 30
+//! collectors = ["volumes", "aggregates", "nodes"]
+//!
+//! [[clusters]]
+//! name = "cluster-01"
+//! url = "https://cluster-01.example.com/api"
+//! username = "monitoring"
+//! password = "secret"
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fmt::Write,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::{
+    models::{MetricsQuery, OntapApiError, Status},
+    OntapClient, OntapConnectionParams,
+};
+
+const UP_HELP: &str = "Whether the cluster could be reached";
+const CONTENT_TYPE_OPENMETRICS: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Error, Debug)]
+pub enum ExporterError {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid configuration")]
+    Config(#[from] toml::de::Error),
+    #[error("HTTP server error")]
+    Server(#[from] hyper::Error),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExporterConfig {
+    pub listen: SocketAddr,
+    #[serde(default = "default_scrape_timeout")]
+    pub scrape_timeout_secs: u64,
+    #[serde(default = "Collector::all")]
+    pub collectors: Vec<Collector>,
+    pub clusters: Vec<ClusterConfig>,
+}
+
+fn default_scrape_timeout() -> u64 {
+    30
+}
+
+impl ExporterConfig {
+    pub fn from_toml(config: &str) -> Result<Self, ExporterError> {
+        Ok(toml::from_str(config)?)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ClusterConfig {
+    /// Value of the `cluster` label.
+    pub name: String,
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// Overrides the global collectors for this cluster.
+    pub collectors: Option<Vec<Collector>>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Collector {
+    Volumes,
+    Aggregates,
+    Disks,
+    Shelves,
+    Nodes,
+    Lifs,
+    Ports,
+    Snapmirror,
+    Metrics,
+}
+
+impl Collector {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Collector::Volumes,
+            Collector::Aggregates,
+            Collector::Disks,
+            Collector::Shelves,
+            Collector::Nodes,
+            Collector::Lifs,
+            Collector::Ports,
+            Collector::Snapmirror,
+            Collector::Metrics,
+        ]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Collector::Volumes => "volumes",
+            Collector::Aggregates => "aggregates",
+            Collector::Disks => "disks",
+            Collector::Shelves => "shelves",
+            Collector::Nodes => "nodes",
+            Collector::Lifs => "lifs",
+            Collector::Ports => "ports",
+            Collector::Snapmirror => "snapmirror",
+            Collector::Metrics => "metrics",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Family {
+    help: String,
+    samples: Vec<(String, f64)>,
+}
+
+/// Gauge families rendered in the OpenMetrics text format.
+#[derive(Debug, Default)]
+pub struct MetricSet {
+    families: BTreeMap<String, Family>,
+}
+
+impl MetricSet {
+    pub fn gauge(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        let family = self
+            .families
+            .entry(name.to_string())
+            .or_insert_with(|| Family {
+                help: help.to_string(),
+                samples: vec![],
+            });
+        let labels = labels
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{}\"", escape(v)))
+            .collect::<Vec<_>>()
+            .join(",");
+        family.samples.push((labels, value));
+    }
+
+    pub fn extend(&mut self, other: MetricSet) {
+        for (name, family) in other.families {
+            let entry = self.families.entry(name).or_insert_with(|| Family {
+                help: family.help,
+                samples: vec![],
+            });
+            entry.samples.extend(family.samples);
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, family) in &self.families {
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "# HELP {name} {}", escape(&family.help));
+            for (labels, value) in &family.samples {
+                let _ = writeln!(out, "{name}{{{labels}}} {value}");
+            }
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// snake_case name of a serde enum, used as label value.
+fn state<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s.to_lowercase(),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Runs one collector against a connected client.
+pub async fn collect(
+    cluster: &str,
+    client: &OntapClient,
+    collector: Collector,
+) -> Result<MetricSet, OntapApiError> {
+    let mut m = MetricSet::default();
+    match collector {
+        Collector::Volumes => {
+            for v in client.get_volumes().await?.records {
+                let labels = [
+                    ("cluster", cluster),
+                    ("svm", v.svm.name.as_str()),
+                    ("volume", v.name.as_str()),
+                ];
+                let help = "Volume size in bytes";
+                m.gauge("ontap_volume_size_bytes", help, &labels, v.size as f64);
+                let help = "Used volume space in bytes";
+                m.gauge(
+                    "ontap_volume_used_bytes",
+                    help,
+                    &labels,
+                    v.space.used as f64,
+                );
+                let help = "Available volume space in bytes";
+                let available = v.space.available as f64;
+                m.gauge("ontap_volume_available_bytes", help, &labels, available);
+                let help = "Used files (inodes) of the volume";
+                m.gauge(
+                    "ontap_volume_files_used",
+                    help,
+                    &labels,
+                    v.files.used as f64,
+                );
+                let help = "Maximum files (inodes) of the volume";
+                let maximum = v.files.maximum as f64;
+                m.gauge("ontap_volume_files_maximum", help, &labels, maximum);
+            }
+        }
+        Collector::Aggregates => {
+            for a in client.get_aggregates().await?.records {
+                let node = a.node.as_ref().map(|n| n.name.as_str()).unwrap_or_default();
+                let labels = [
+                    ("cluster", cluster),
+                    ("node", node),
+                    ("aggregate", a.name.as_str()),
+                ];
+                let space = &a.space.block_storage;
+                let help = "Aggregate size in bytes";
+                m.gauge(
+                    "ontap_aggregate_size_bytes",
+                    help,
+                    &labels,
+                    space.size as f64,
+                );
+                let help = "Used aggregate space in bytes";
+                m.gauge(
+                    "ontap_aggregate_used_bytes",
+                    help,
+                    &labels,
+                    space.used as f64,
+                );
+                let help = "Available aggregate space in bytes";
+                let available = space.available as f64;
+                m.gauge("ontap_aggregate_available_bytes", help, &labels, available);
+            }
+        }
+        Collector::Disks => {
+            for d in client.get_disks().await?.records {
+                let state = state(&d.state);
+                let labels = [
+                    ("cluster", cluster),
+                    ("node", d.node_name().unwrap_or_default()),
+                    ("disk", d.name.as_str()),
+                ];
+                let help = "Disk state, 1 for the current state";
+                let with_state = [labels[0], labels[1], labels[2], ("state", state.as_str())];
+                m.gauge("ontap_disk_state", help, &with_state, 1.0);
+                if let Some(used) = d.rated_life_used_percent {
+                    let help = "Percentage of the rated SSD life used";
+                    m.gauge(
+                        "ontap_disk_rated_life_used_percent",
+                        help,
+                        &labels,
+                        used as f64,
+                    );
+                }
+            }
+        }
+        Collector::Shelves => {
+            for s in client.get_shelves().await?.records {
+                let state = state(&s.state);
+                let labels = [
+                    ("cluster", cluster),
+                    ("shelf", s.name.as_str()),
+                    ("state", state.as_str()),
+                ];
+                m.gauge(
+                    "ontap_shelf_state",
+                    "Shelf state, 1 for the current state",
+                    &labels,
+                    1.0,
+                );
+                for t in s.temperature_sensors.iter().flatten() {
+                    let Some(temperature) = t.temperature else {
+                        continue;
+                    };
+                    let id = t.id.to_string();
+                    let labels = [
+                        ("cluster", cluster),
+                        ("shelf", s.name.as_str()),
+                        ("sensor", id.as_str()),
+                    ];
+                    let help = "Shelf temperature sensor reading in degrees Celsius";
+                    let temperature = temperature as f64;
+                    m.gauge(
+                        "ontap_shelf_temperature_celsius",
+                        help,
+                        &labels,
+                        temperature,
+                    );
+                }
+            }
+        }
+        Collector::Nodes => {
+            for n in client.get_nodes().await?.records {
+                let state = state(&n.state);
+                let labels = [("cluster", cluster), ("node", n.name.as_str())];
+                let help = "Node state, 1 for the current state";
+                let with_state = [labels[0], labels[1], ("state", state.as_str())];
+                m.gauge("ontap_node_state", help, &with_state, 1.0);
+                let help = "Node uptime in seconds";
+                m.gauge("ontap_node_uptime_seconds", help, &labels, n.uptime as f64);
+            }
+        }
+        Collector::Lifs => {
+            for l in client.get_ip_interfaces().await?.records {
+                let svm = l.svm.as_ref().map(|s| s.name.as_str()).unwrap_or("");
+                let labels = [
+                    ("cluster", cluster),
+                    ("node", l.location.node.name.as_str()),
+                    ("svm", svm),
+                    ("lif", l.name.as_str()),
+                ];
+                let up = state(&l.state) == "up";
+                m.gauge(
+                    "ontap_lif_up",
+                    "Whether the LIF is up",
+                    &labels,
+                    bool_value(up),
+                );
+                let help = "Whether the LIF is on its home port";
+                let home = bool_value(l.location.is_home);
+                m.gauge("ontap_lif_is_home", help, &labels, home);
+            }
+        }
+        Collector::Ports => {
+            for p in client.get_ethernet_ports().await?.records {
+                let labels = [
+                    ("cluster", cluster),
+                    ("node", p.node.name.as_str()),
+                    ("port", p.name.as_str()),
+                ];
+                let up = state(&p.state) == "up";
+                m.gauge(
+                    "ontap_port_up",
+                    "Whether the port link is up",
+                    &labels,
+                    bool_value(up),
+                );
+                if let Some(speed) = p.speed {
+                    let help = "Negotiated port speed in Mbit/s";
+                    m.gauge("ontap_port_speed_mbps", help, &labels, speed as f64);
+                }
+            }
+        }
+        Collector::Snapmirror => {
+            for r in client.get_snapmirror_relationships().await?.records {
+                let state = state(&r.state);
+                let labels = [
+                    ("cluster", cluster),
+                    ("svm", r.destination.svm.name.as_str()),
+                    ("source", r.source.path.as_str()),
+                    ("destination", r.destination.path.as_str()),
+                ];
+                let help = "Whether the SnapMirror relationship is healthy";
+                let healthy = bool_value(r.healthy);
+                m.gauge("ontap_snapmirror_healthy", help, &labels, healthy);
+                let help = "SnapMirror relationship state, 1 for the current state";
+                let with_state = [
+                    labels[0],
+                    labels[1],
+                    labels[2],
+                    labels[3],
+                    ("state", &state),
+                ];
+                m.gauge("ontap_snapmirror_state", help, &with_state, 1.0);
+            }
+        }
+        Collector::Metrics => {
+            let query = MetricsQuery::default();
+            let series = client.get_cluster_metrics(&query).await?;
+            if let Some(r) = series.latest().filter(|r| r.status == Status::Ok) {
+                for (direction, iops, throughput, latency) in [
+                    (
+                        "read",
+                        r.iops.read,
+                        r.throughput.read,
+                        r.latency.as_ref().map(|l| l.read),
+                    ),
+                    (
+                        "write",
+                        r.iops.write,
+                        r.throughput.write,
+                        r.latency.as_ref().map(|l| l.write),
+                    ),
+                    (
+                        "other",
+                        r.iops.other,
+                        r.throughput.other,
+                        r.latency.as_ref().map(|l| l.other),
+                    ),
+                ] {
+                    let labels = [("cluster", cluster), ("direction", direction)];
+                    let help = "Cluster operations per second";
+                    m.gauge("ontap_cluster_iops", help, &labels, iops as f64);
+                    let help = "Cluster throughput in bytes per second";
+                    let throughput = throughput as f64;
+                    m.gauge(
+                        "ontap_cluster_throughput_bytes_per_second",
+                        help,
+                        &labels,
+                        throughput,
+                    );
+                    if let Some(latency) = latency {
+                        let help = "Cluster average latency in microseconds";
+                        let latency = latency as f64;
+                        m.gauge("ontap_cluster_latency_microseconds", help, &labels, latency);
+                    }
+                }
+            }
+            for n in client.get_nodes().await?.records {
+                let series = client.get_node_metrics(&n.uuid, &query).await?;
+                let utilization = series
+                    .latest()
+                    .filter(|r| r.status == Status::Ok)
+                    .and_then(|r| r.processor_utilization);
+                if let Some(utilization) = utilization {
+                    let labels = [("cluster", cluster), ("node", n.name.as_str())];
+                    let help = "Node processor utilization in percent";
+                    let utilization = utilization as f64;
+                    m.gauge(
+                        "ontap_node_processor_utilization_percent",
+                        help,
+                        &labels,
+                        utilization,
+                    );
+                }
+            }
+        }
+    }
+    Ok(m)
+}
+
+/// Connected clients by cluster name, kept across scrapes.
+type Clients = Arc<Mutex<BTreeMap<String, Arc<OntapClient>>>>;
+
+async fn connect(cluster: ClusterConfig, clients: &Clients) -> Result<Arc<OntapClient>, String> {
+    if let Some(client) = clients
+        .lock()
+        .ok()
+        .and_then(|c| c.get(&cluster.name).cloned())
+    {
+        return Ok(client);
+    }
+    let url = Url::parse(&cluster.url).map_err(|e| e.to_string())?;
+    let client = OntapConnectionParams {
+        url,
+        username: cluster.username,
+        password: cluster.password,
+    }
+    .connect()
+    .await
+    .map_err(|e| e.to_string())?;
+    let client = Arc::new(client);
+    if let Ok(mut clients) = clients.lock() {
+        clients.insert(cluster.name, client.clone());
+    }
+    Ok(client)
+}
+
+async fn scrape_cluster(
+    cluster: ClusterConfig,
+    collectors: Vec<Collector>,
+    clients: Clients,
+) -> MetricSet {
+    let mut m = MetricSet::default();
+    let name = cluster.name.clone();
+    let client = match connect(cluster, &clients).await {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("failed to connect to cluster {name}: {e}");
+            m.gauge("ontap_up", UP_HELP, &[("cluster", &name)], 0.0);
+            return m;
+        }
+    };
+    m.gauge("ontap_up", UP_HELP, &[("cluster", &name)], 1.0);
+    let mut any_success = collectors.is_empty();
+    for collector in collectors {
+        let labels = [
+            ("cluster", name.as_str()),
+            ("collector", collector.as_str()),
+        ];
+        let help = "Whether the last run of the collector succeeded";
+        match collect(&name, &client, collector).await {
+            Ok(metrics) => {
+                m.extend(metrics);
+                m.gauge("ontap_collector_success", help, &labels, 1.0);
+                any_success = true;
+            }
+            Err(e) => {
+                warn!(
+                    "collector {} failed for cluster {name}: {e}",
+                    collector.as_str()
+                );
+                m.gauge("ontap_collector_success", help, &labels, 0.0);
+            }
+        }
+    }
+    // reconnect on the next scrape, e.g. after a takeover moved the management LIF
+    if !any_success {
+        if let Ok(mut clients) = clients.lock() {
+            clients.remove(&name);
+        }
+    }
+    m
+}
+
+/// Scrapes the configured clusters. Clients are connected on the first scrape of a cluster and
+/// reused afterwards.
+pub struct Exporter {
+    config: ExporterConfig,
+    clients: Clients,
+}
+
+impl Exporter {
+    pub fn new(config: ExporterConfig) -> Self {
+        Self {
+            config,
+            clients: Arc::default(),
+        }
+    }
+
+    /// Scrapes all configured clusters concurrently, each bounded by the scrape timeout.
+    pub async fn scrape(&self) -> String {
+        scrape(&self.config, &self.clients).await
+    }
+}
+
+async fn scrape(config: &ExporterConfig, clients: &Clients) -> String {
+    let timeout = std::time::Duration::from_secs(config.scrape_timeout_secs);
+    let tasks = config
+        .clusters
+        .iter()
+        .map(|cluster| {
+            let collectors = cluster
+                .collectors
+                .clone()
+                .unwrap_or_else(|| config.collectors.clone());
+            let name = cluster.name.clone();
+            let scrape = scrape_cluster(cluster.clone(), collectors, clients.clone());
+            let task = tokio::spawn(async move {
+                let started = Instant::now();
+                let metrics = tokio::time::timeout(timeout, scrape).await;
+                (metrics, started.elapsed())
+            });
+            (name, task)
+        })
+        .collect::<Vec<_>>();
+
+    let mut m = MetricSet::default();
+    for (name, task) in tasks {
+        let elapsed = match task.await {
+            Ok((Ok(metrics), elapsed)) => {
+                m.extend(metrics);
+                Some(elapsed)
+            }
+            Ok((Err(_), elapsed)) => {
+                warn!("scrape of cluster {name} timed out");
+                m.gauge("ontap_up", UP_HELP, &[("cluster", &name)], 0.0);
+                Some(elapsed)
+            }
+            Err(e) => {
+                warn!("scrape of cluster {name} failed: {e}");
+                m.gauge("ontap_up", UP_HELP, &[("cluster", &name)], 0.0);
+                None
+            }
+        };
+        if let Some(elapsed) = elapsed {
+            let help = "Time the scrape of the cluster took in seconds";
+            let elapsed = elapsed.as_secs_f64();
+            m.gauge(
+                "ontap_scrape_duration_seconds",
+                help,
+                &[("cluster", &name)],
+                elapsed,
+            );
+        }
+    }
+    m.render()
+}
+
+/// Serves `/metrics` until the process is stopped.
+pub async fn serve(config: ExporterConfig) -> Result<(), ExporterError> {
+    let addr = config.listen;
+    let exporter = Arc::new(Exporter::new(config));
+    let make_service = make_service_fn(move |_| {
+        let exporter = exporter.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |req| handle(req, exporter.clone()))) }
+    });
+    Server::try_bind(&addr)?.serve(make_service).await?;
+    Ok(())
+}
+
+async fn handle(req: Request<Body>, exporter: Arc<Exporter>) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET || req.uri().path() != "/metrics" {
+        let mut res = Response::new(Body::empty());
+        *res.status_mut() = StatusCode::NOT_FOUND;
+        return Ok(res);
+    }
+    let mut res = Response::new(Body::from(exporter.scrape().await));
+    res.headers_mut()
+        .insert(CONTENT_TYPE, CONTENT_TYPE_OPENMETRICS.parse().unwrap());
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server, StatusCode,
+    };
+    use serde_json::json;
+
+    use super::{ClusterConfig, Collector, Exporter, ExporterConfig};
+
+    static CONNECTS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Stand-in for the ONTAP REST API, answering a fixed set of paths.
+    async fn stand_in(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let body = match req.uri().path() {
+            "/slow/api/cluster" => {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                json!({})
+            }
+            "/api/cluster" => {
+                CONNECTS.fetch_add(1, Ordering::SeqCst);
+                json!({
+                    "version": { "full": "NetApp Release 9.10.1", "generation": 9, "major": 10, "minor": 1 }
+                })
+            }
+            "/api/storage/aggregates" => json!({
+                "num_records": 1,
+                "records": [{
+                    "uuid": "ag1", "name": "aggr1",
+                    "node": { "uuid": "n1", "name": "node-01" },
+                    "space": {
+                        "block_storage": {
+                            "size": 1000, "available": 300, "used": 700, "full_threshold_percent": 98
+                        },
+                        "efficiency": { "savings": 0, "ratio": 1.0, "logical_used": 700 }
+                    },
+                    "block_storage": {
+                        "primary": {
+                            "disk_class": "solid_state", "disk_count": 8, "raid_size": 24,
+                            "raid_type": "raid_dp"
+                        }
+                    }
+                }]
+            }),
+            "/api/storage/volumes" => json!({
+                "num_records": 1,
+                "records": [{
+                    "uuid": "a1b2", "name": "vol\"1", "size": 1000,
+                    "svm": { "uuid": "s1", "name": "svm1" },
+                    "aggregates": [{ "uuid": "ag1", "name": "aggr1" }],
+                    "encryption": { "enabled": false },
+                    "space": {
+                        "available": 400, "over_provisioned": 0, "size": 1000, "used": 600,
+                        "snapshot": { "autodelete_enabled": false, "reserve_percent": 5, "used": 0 }
+                    },
+                    "clone": { "is_flexclone": false },
+                    "autosize": {
+                        "grow_threshold": 90, "maximum": 2000, "minimum": 1000,
+                        "mode": "off", "shrink_threshold": 50
+                    },
+                    "files": { "maximum": 100, "used": 10 }
+                }]
+            }),
+            "/api/snapmirror/relationships" => json!({
+                "num_records": 1,
+                "records": [{
+                    "uuid": "r1",
+                    "source": { "path": "svm0:vol1", "svm": { "name": "svm0" } },
+                    "destination": { "path": "svm1:vol1_dst", "svm": { "name": "svm1" } },
+                    "state": "snapmirrored",
+                    "healthy": true
+                }]
+            }),
+            _ => {
+                let mut res = Response::new(Body::empty());
+                *res.status_mut() = StatusCode::NOT_FOUND;
+                return Ok(res);
+            }
+        };
+        Ok(Response::new(Body::from(body.to_string())))
+    }
+
+    fn cluster(name: &str, url: String) -> ClusterConfig {
+        ClusterConfig {
+            name: name.to_string(),
+            url,
+            username: "admin".to_string(),
+            password: "secret".to_string(),
+            collectors: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn scrape_stand_in() {
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service_fn(
+            |_| async { Ok::<_, Infallible>(service_fn(stand_in)) },
+        ));
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let config = ExporterConfig {
+            listen: "127.0.0.1:0".parse().unwrap(),
+            scrape_timeout_secs: 1,
+            collectors: vec![
+                Collector::Volumes,
+                Collector::Aggregates,
+                Collector::Snapmirror,
+                Collector::Disks,
+            ],
+            clusters: vec![
+                cluster("c1", format!("http://{addr}/api")),
+                cluster("c2", format!("http://{addr}/slow/api")),
+            ],
+        };
+        let exporter = Exporter::new(config);
+        let out = exporter.scrape().await;
+
+        assert!(out.contains("# TYPE ontap_volume_used_bytes gauge\n"));
+        assert!(
+            out.contains(r#"ontap_volume_used_bytes{cluster="c1",svm="svm1",volume="vol\"1"} 600"#)
+        );
+        assert!(out.contains(
+            r#"ontap_snapmirror_state{cluster="c1",svm="svm1",source="svm0:vol1",destination="svm1:vol1_dst",state="snapmirrored"} 1"#
+        ));
+        assert!(out.contains(r#"ontap_collector_success{cluster="c1",collector="disks"} 0"#));
+        assert!(out.contains(r#"ontap_up{cluster="c1"} 1"#));
+        assert!(out.contains(r#"ontap_up{cluster="c2"} 0"#));
+        assert!(out.ends_with("# EOF\n"));
+        assert!(out.contains(
+            r#"ontap_aggregate_used_bytes{cluster="c1",node="node-01",aggregate="aggr1"} 700"#
+        ));
+
+        exporter.scrape().await;
+        assert_eq!(1, CONNECTS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn parse_config() {
+        let config = ExporterConfig::from_toml(
+            r#"
+            listen = "0.0.0.0:9900"
+            collectors = ["volumes", "nodes"]
+
+            [[clusters]]
+            name = "c1"
+            url = "https://c1.example.com/api"
+            username = "monitoring"
+            password = "secret"
+            collectors = ["metrics"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(30, config.scrape_timeout_secs);
+        assert_eq!(
+            vec![Collector::Volumes, Collector::Nodes],
+            config.collectors
+        );
+        assert_eq!(
+            Some(vec![Collector::Metrics]),
+            config.clusters[0].collectors
+        );
+    }
+}
@@ -0,0 +1,554 @@
+use std::fmt::Display;
+
+use crate::models::{
+    aggregate::{Plex, PlexState},
+    disk::{spare_report, ContainerType, Disk, DiskClass, DiskState, DiskType},
+    sensor::{Chassis, ChassisState, Sensor, SensorThresholdState},
+    shelf::{self, ComponentState, Shelf, ShelfState},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    WornSsd {
+        disk: String,
+        rated_life_used_percent: u32,
+    },
+    BrokenDisk {
+        disk: String,
+    },
+    MissingSpares {
+        node: String,
+        class: DiskClass,
+        spares: u32,
+    },
+    PlexNotNormal {
+        aggregate: String,
+        plex: String,
+        state: PlexState,
+    },
+    DegradedRaidGroup {
+        aggregate: String,
+        raid_group: String,
+    },
+    ReconstructingRaidGroup {
+        aggregate: String,
+        raid_group: String,
+        percent: Option<u64>,
+    },
+    ShelfError {
+        shelf: String,
+    },
+    BayError {
+        shelf: String,
+        bay: u64,
+    },
+    SinglePathShelf {
+        shelf: String,
+        paths: usize,
+    },
+    DisconnectedShelfPort {
+        shelf: String,
+        port: String,
+    },
+    ShelfComponentError {
+        shelf: String,
+        component: String,
+    },
+    SensorAlert {
+        node: String,
+        sensor: String,
+        state: SensorThresholdState,
+    },
+    ChassisFruError {
+        chassis: String,
+        fru: String,
+    },
+}
+
+impl Finding {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::WornSsd { .. } => Severity::Warning,
+            Finding::BrokenDisk { .. } => Severity::Critical,
+            Finding::MissingSpares { spares: 0, .. } => Severity::Critical,
+            Finding::MissingSpares { .. } => Severity::Warning,
+            Finding::PlexNotNormal { .. } => Severity::Critical,
+            Finding::DegradedRaidGroup { .. } => Severity::Critical,
+            Finding::ReconstructingRaidGroup { .. } => Severity::Warning,
+            Finding::ShelfError { .. } => Severity::Critical,
+            Finding::BayError { .. } => Severity::Warning,
+            Finding::SinglePathShelf { paths: 0, .. } => Severity::Critical,
+            Finding::SinglePathShelf { .. } => Severity::Warning,
+            Finding::DisconnectedShelfPort { .. } => Severity::Warning,
+            Finding::ShelfComponentError { .. } => Severity::Critical,
+            Finding::SensorAlert {
+                state: SensorThresholdState::LowWarning | SensorThresholdState::HighWarning,
+                ..
+            } => Severity::Warning,
+            Finding::SensorAlert {
+                state: SensorThresholdState::Unknown,
+                ..
+            } => Severity::Info,
+            Finding::SensorAlert { .. } => Severity::Critical,
+            Finding::ChassisFruError { .. } => Severity::Critical,
+        }
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: ", self.severity())?;
+        match self {
+            Finding::WornSsd {
+                disk,
+                rated_life_used_percent,
+            } => write!(
+                f,
+                "SSD {disk} has used {rated_life_used_percent}% of its rated life"
+            ),
+            Finding::BrokenDisk { disk } => write!(f, "disk {disk} is broken"),
+            Finding::MissingSpares {
+                node,
+                class,
+                spares,
+            } => write!(f, "node {node} has {spares} {class:?} spare(s)"),
+            Finding::PlexNotNormal {
+                aggregate,
+                plex,
+                state,
+            } => write!(f, "plex {aggregate}/{plex} is {state:?}"),
+            Finding::DegradedRaidGroup {
+                aggregate,
+                raid_group,
+            } => write!(f, "RAID group {aggregate}/{raid_group} is degraded"),
+            Finding::ReconstructingRaidGroup {
+                aggregate,
+                raid_group,
+                percent,
+            } => {
+                write!(f, "RAID group {aggregate}/{raid_group} is reconstructing")?;
+                if let Some(percent) = percent {
+                    write!(f, " ({percent}%)")?;
+                }
+                Ok(())
+            }
+            Finding::ShelfError { shelf } => write!(f, "shelf {shelf} is in error state"),
+            Finding::BayError { shelf, bay } => {
+                write!(f, "bay {bay} of shelf {shelf} is in error state")
+            }
+            Finding::SinglePathShelf { shelf, paths } => {
+                write!(f, "shelf {shelf} has {paths} path(s)")
+            }
+            Finding::DisconnectedShelfPort { shelf, port } => {
+                write!(f, "port {port} of shelf {shelf} is disconnected")
+            }
+            Finding::ShelfComponentError { shelf, component } => {
+                write!(f, "{component} of shelf {shelf} is in error state")
+            }
+            Finding::SensorAlert {
+                node,
+                sensor,
+                state,
+            } => write!(f, "sensor {sensor} on node {node} is {state:?}"),
+            Finding::ChassisFruError { chassis, fru } => {
+                write!(f, "{fru} of chassis {chassis} is in error state")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthThresholds {
+    /// SSDs at or above this percentage of rated life are reported as worn.
+    pub worn_ssd_percent: u32,
+    /// Minimum number of spares each node should hold per disk class in use.
+    pub min_spares: u32,
+    /// Minimum number of paths each shelf should be reachable through.
+    pub min_shelf_paths: usize,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            worn_ssd_percent: 80,
+            min_spares: 1,
+            min_shelf_paths: 2,
+        }
+    }
+}
+
+/// Only whole-disk spares are checked, partitioned (`shared`) disks keep their spare capacity in
+/// partitions and don't require a spare disk.
+pub fn assess_disks(disks: &[Disk], thresholds: &HealthThresholds) -> Vec<Finding> {
+    let mut findings = vec![];
+
+    for disk in disks {
+        if disk.state == DiskState::Broken {
+            findings.push(Finding::BrokenDisk {
+                disk: disk.name.clone(),
+            });
+        }
+        let is_ssd = matches!(
+            disk.typ,
+            DiskType::Ssd | DiskType::SsdCap | DiskType::SsdNvm
+        );
+        if let Some(used) = disk.rated_life_used_percent {
+            if is_ssd && used >= thresholds.worn_ssd_percent {
+                findings.push(Finding::WornSsd {
+                    disk: disk.name.clone(),
+                    rated_life_used_percent: used,
+                });
+            }
+        }
+    }
+
+    let spares = spare_report(disks);
+    let mut in_use = disks
+        .iter()
+        .filter(|d| d.container_type == ContainerType::Aggregate)
+        .filter_map(|d| Some((d.node_name()?.to_string(), d.class.clone())))
+        .collect::<Vec<_>>();
+    in_use.sort();
+    in_use.dedup();
+    for (node, class) in in_use {
+        let count = spares
+            .iter()
+            .find(|s| s.node == node && s.class == class)
+            .map(|s| s.count)
+            .unwrap_or(0);
+        if count < thresholds.min_spares {
+            findings.push(Finding::MissingSpares {
+                node,
+                class,
+                spares: count,
+            });
+        }
+    }
+
+    findings
+}
+
+pub fn assess_plexes(plexes: &[Plex]) -> Vec<Finding> {
+    let mut findings = vec![];
+
+    for plex in plexes {
+        if plex.state != PlexState::Normal {
+            findings.push(Finding::PlexNotNormal {
+                aggregate: plex.aggregate.name.clone(),
+                plex: plex.name.clone(),
+                state: plex.state.clone(),
+            });
+        }
+        for rg in &plex.raid_groups {
+            if rg.degraded {
+                findings.push(Finding::DegradedRaidGroup {
+                    aggregate: plex.aggregate.name.clone(),
+                    raid_group: rg.name.clone(),
+                });
+            }
+            if rg.reconstruct.active {
+                findings.push(Finding::ReconstructingRaidGroup {
+                    aggregate: plex.aggregate.name.clone(),
+                    raid_group: rg.name.clone(),
+                    percent: rg.reconstruct.percent,
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+/// Shelf ports are only reported as disconnected when a cable is plugged in, unused ports are
+/// ignored.
+pub fn assess_shelves(shelves: &[Shelf], thresholds: &HealthThresholds) -> Vec<Finding> {
+    let mut findings = vec![];
+
+    for s in shelves {
+        if let ShelfState::Error = s.state {
+            findings.push(Finding::ShelfError {
+                shelf: s.name.clone(),
+            });
+        }
+        for bay in &s.bays {
+            if let shelf::DiskState::Error = bay.state {
+                findings.push(Finding::BayError {
+                    shelf: s.name.clone(),
+                    bay: bay.id,
+                });
+            }
+        }
+        if s.paths.len() < thresholds.min_shelf_paths {
+            findings.push(Finding::SinglePathShelf {
+                shelf: s.name.clone(),
+                paths: s.paths.len(),
+            });
+        }
+        for port in s.ports.iter().flatten() {
+            let disconnected = match port.state {
+                shelf::PortState::Connected => false,
+                shelf::PortState::Disconnected => port.cable.is_some(),
+                shelf::PortState::Error => true,
+            };
+            if disconnected {
+                findings.push(Finding::DisconnectedShelfPort {
+                    shelf: s.name.clone(),
+                    port: port.designator.clone(),
+                });
+            }
+        }
+
+        let components = s
+            .frus
+            .iter()
+            .flatten()
+            .map(|fru| (format!("{:?} {}", fru.typ, fru.id), &fru.state))
+            .chain(
+                s.fans
+                    .iter()
+                    .flatten()
+                    .map(|fan| (format!("fan {}", fan.id), &fan.state)),
+            )
+            .chain(
+                s.temperature_sensors
+                    .iter()
+                    .flatten()
+                    .map(|t| (format!("temperature sensor {}", t.id), &t.state)),
+            )
+            .chain(
+                s.voltage_sensors
+                    .iter()
+                    .flatten()
+                    .map(|v| (format!("voltage sensor {}", v.id), &v.state)),
+            )
+            .chain(
+                s.current_sensors
+                    .iter()
+                    .flatten()
+                    .map(|c| (format!("current sensor {}", c.id), &c.state)),
+            );
+        for (component, state) in components {
+            if *state == ComponentState::Error {
+                findings.push(Finding::ShelfComponentError {
+                    shelf: s.name.clone(),
+                    component,
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+pub fn assess_sensors(sensors: &[Sensor]) -> Vec<Finding> {
+    sensors
+        .iter()
+        .filter(|s| !s.is_normal())
+        .map(|s| Finding::SensorAlert {
+            node: s.node.name.clone(),
+            sensor: s.name.clone(),
+            state: s
+                .threshold_state
+                .clone()
+                .unwrap_or(SensorThresholdState::Unknown),
+        })
+        .collect()
+}
+
+pub fn assess_chassis(chassis: &[Chassis]) -> Vec<Finding> {
+    chassis
+        .iter()
+        .flat_map(|c| {
+            c.frus
+                .iter()
+                .flatten()
+                .filter(|fru| fru.state == ChassisState::Error)
+                .map(|fru| Finding::ChassisFruError {
+                    chassis: c.id.clone(),
+                    fru: format!("{:?} {}", fru.typ, fru.id),
+                })
+        })
+        .collect()
+}
+
+/// Runs all checks and returns the findings ordered by descending severity.
+pub fn assess(
+    disks: &[Disk],
+    shelves: &[Shelf],
+    plexes: &[Plex],
+    thresholds: &HealthThresholds,
+) -> Vec<Finding> {
+    let mut findings = assess_disks(disks, thresholds);
+    findings.extend(assess_shelves(shelves, thresholds));
+    findings.extend(assess_plexes(plexes));
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity()));
+    findings
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{assess, assess_shelves, Finding, HealthThresholds, Severity};
+    use crate::models::{aggregate::Plex, disk::Disk, shelf::Shelf};
+
+    fn disk(name: &str, container_type: &str, state: &str, life: u32) -> Disk {
+        serde_json::from_value(json!({
+            "bay": 0,
+            "class": "solid_state",
+            "container_type": container_type,
+            "firmware_version": "NA02",
+            "home_node": { "uuid": "n1", "name": "node-01" },
+            "model": "X357_S163A3T8ATE",
+            "name": name,
+            "node": { "uuid": "n1", "name": "node-01" },
+            "pool": "pool0",
+            "rated_life_used_percent": life,
+            "self_encrypting": false,
+            "serial_number": name,
+            "state": state,
+            "type": "ssd",
+            "uid": name,
+            "usable_size": 100,
+            "vendor": "NETAPP",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn findings() {
+        let disks = vec![
+            disk("1.0.0", "aggregate", "present", 10),
+            disk("1.0.1", "aggregate", "present", 95),
+            disk("1.0.2", "broken", "broken", 10),
+        ];
+        let shelves: Vec<Shelf> = serde_json::from_value(json!([{
+            "bays": [
+                { "has_disk": true, "id": 0, "state": "ok", "type": "single_disk" },
+                { "has_disk": true, "id": 1, "state": "error", "type": "single_disk" },
+            ],
+            "connection_type": "sas",
+            "disk_count": 2,
+            "id": "1",
+            "uid": "1",
+            "model": "DS224-12",
+            "name": "1.0",
+            "paths": [{ "name": "0a", "node": { "uuid": "n1", "name": "node-01" } }],
+            "ports": [
+                { "designator": "square", "id": 0, "internal": false, "module_id": "a", "state": "disconnected" },
+                { "cable": { "identifier": "c1" }, "designator": "circle", "id": 1, "internal": false, "module_id": "a", "state": "disconnected" },
+            ],
+            "state": "ok",
+        }]))
+        .unwrap();
+        let plexes: Vec<Plex> = serde_json::from_value(json!([{
+            "aggregate": { "name": "aggr1" },
+            "name": "plex0",
+            "online": true,
+            "pool": "pool0",
+            "raid_groups": [{
+                "cache_tier": false,
+                "degraded": true,
+                "disks": [],
+                "name": "rg0",
+                "recomputing_parity": { "active": false },
+                "reconstruct": { "active": true, "percent": 42 },
+            }],
+            "resync": { "active": false },
+            "state": "normal",
+        }]))
+        .unwrap();
+
+        let findings = assess(&disks, &shelves, &plexes, &HealthThresholds::default());
+
+        assert_eq!(
+            vec![
+                Finding::BrokenDisk {
+                    disk: "1.0.2".into()
+                },
+                Finding::MissingSpares {
+                    node: "node-01".into(),
+                    class: crate::models::disk::DiskClass::SolidState,
+                    spares: 0,
+                },
+                Finding::DegradedRaidGroup {
+                    aggregate: "aggr1".into(),
+                    raid_group: "rg0".into(),
+                },
+                Finding::WornSsd {
+                    disk: "1.0.1".into(),
+                    rated_life_used_percent: 95,
+                },
+                Finding::BayError {
+                    shelf: "1.0".into(),
+                    bay: 1,
+                },
+                Finding::SinglePathShelf {
+                    shelf: "1.0".into(),
+                    paths: 1,
+                },
+                Finding::DisconnectedShelfPort {
+                    shelf: "1.0".into(),
+                    port: "circle".into(),
+                },
+                Finding::ReconstructingRaidGroup {
+                    aggregate: "aggr1".into(),
+                    raid_group: "rg0".into(),
+                    percent: Some(42),
+                },
+            ],
+            findings
+        );
+        assert_eq!(Severity::Critical, findings[0].severity());
+    }
+
+    #[test]
+    fn partitioned_disks() {
+        let disks = vec![
+            disk("1.0.0", "shared", "present", 10),
+            disk("1.0.1", "shared", "present", 10),
+        ];
+
+        let findings = assess(&disks, &[], &[], &HealthThresholds::default());
+
+        assert_eq!(Vec::<Finding>::new(), findings);
+    }
+
+    #[test]
+    fn failed_psu() {
+        let shelves: Vec<Shelf> = serde_json::from_value(json!([{
+            "bays": [],
+            "connection_type": "sas",
+            "disk_count": 0,
+            "id": "2",
+            "uid": "2",
+            "model": "DS224-12",
+            "name": "2.0",
+            "paths": [
+                { "name": "0a", "node": { "uuid": "n1", "name": "node-01" } },
+                { "name": "0b", "node": { "uuid": "n2", "name": "node-02" } },
+            ],
+            "frus": [
+                { "id": 1, "type": "psu", "state": "ok" },
+                { "id": 2, "type": "psu", "state": "error" },
+            ],
+            "state": "ok",
+        }]))
+        .unwrap();
+
+        assert_eq!(
+            vec![Finding::ShelfComponentError {
+                shelf: "2.0".into(),
+                component: "Psu 2".into(),
+            }],
+            assess_shelves(&shelves, &HealthThresholds::default())
+        );
+    }
+}
@@ -1,30 +1,120 @@
+use health::{Finding, HealthThresholds};
 use itertools::Itertools;
-use reqwest::{ClientBuilder, Url};
+use reqwest::{ClientBuilder, Method, RequestBuilder, Response, StatusCode, Url};
+use sampler::{StatisticsRates, StatisticsSampler};
+use serde::{de::DeserializeOwned, Deserialize};
+use telemetry::{RequestEvent, RequestObserver};
 
 use models::{
-    aggregate::{AggregateMetricRecords, AggregateRecords, PlexRecords},
-    disk::DiskRecords,
+    aggregate::{
+        AggregateCreateBlockStorage, AggregateCreateBody, AggregateExpandBlockStorage,
+        AggregateExpandBody, AggregateExpandPrimary, AggregateMetricRecords, AggregateMirror,
+        AggregatePrimaryBlockStorage, AggregateRecommendationRecords, AggregateRecords,
+        AggregateRenameBody, PlexRecords, RaidGroup,
+    },
+    broadcast_domain::{
+        BroadcastDomainCreateBody, BroadcastDomainModifyBody, BroadcastDomainRecords,
+    },
+    cluster_peer::{
+        ClusterPeerCreateAuthentication, ClusterPeerCreateBody, ClusterPeerCreateRemote,
+        ClusterPeerModifyBody, ClusterPeerRecords, ClusterPeerSetup, ClusterPeerSetupRecords,
+    },
+    counter::{CounterRow, CounterRowRecords, CounterTable, CounterTableRecords},
+    disk::{
+        DiskEncryptionOperation, DiskEncryptionOperationBody, DiskOwnershipBody, DiskRecords,
+        DiskState, DiskStateBody,
+    },
+    dns::{DnsCreateBody, DnsModifyBody, DnsRecords},
+    ems::{
+        EmsDestination, EmsDestinationModifyBody, EmsDestinationRecords, EmsEvent, EmsEventCursor,
+        EmsEventFilter, EmsEventRecords, EmsFilter, EmsFilterModifyBody, EmsFilterRecords,
+        EmsFilterRule, EmsMessageRecords,
+    },
+    flexcache::{FlexcacheCreateBody, FlexcacheOriginRecords, FlexcacheRecords},
     ip_interface::{
         IpInterfaceAdminState, IpInterfaceHomeState, IpInterfaceHomeStateLocation,
         IpInterfaceRecords,
     },
-    job::JobRecords,
+    ipspace::{IpspaceBody, IpspaceRecords},
+    job::{Job, JobRecords, JobResponse},
+    ldap::{LdapBody, LdapRecords},
+    local_host::{LocalHostCreateBody, LocalHostRecords},
+    name_mapping::{NameMappingCreateBody, NameMappingDirection, NameMappingRecords},
     nfs::NfsClientRecords,
-    node::NodeRecords,
-    port::PortRecords,
+    nis::{NisCreateBody, NisModifyBody, NisRecords},
+    node::{
+        NodeHa, NodeHaOnly, NodeRecords, NodeServiceProcessorAction,
+        NodeServiceProcessorActionBody, NodeServiceProcessorActionOnly,
+    },
+    ntp::{NtpServerCreateBody, NtpServerRecords, NtpVersion},
+    port::{
+        PortBroadcastDomainRef, PortCreateBody, PortCreateLag, PortCreateVlan,
+        PortLagDistributionPolicy, PortLagMode, PortModifyBody, PortRecords, PortType,
+    },
+    qos::{
+        LunQosBody, QosPolicyCreateBody, QosPolicyModifyBody, QosPolicyRecords, QosPolicyRef,
+        QosWorkloadRecords, VolumeQosBody, VolumeQosPolicy,
+    },
+    route::{NetworkRouteCreateBody, NetworkRouteRecords},
+    s3::S3BucketRecords,
+    sensor::{ChassisRecords, SensorRecords},
     shelf::ShelfRecords,
     snapmirror::SnapmirrorRelationshipRecords,
     snapshot::SnapshotRecords,
-    svm::SvmRecords,
+    svm::{NsswitchSource, Svm, SvmRecords},
+    svm_peer::{
+        SvmPeerApplication, SvmPeerCreateBody, SvmPeerCreatePeer, SvmPeerModifyBody,
+        SvmPeerRecords, SvmPeerState,
+    },
+    unix_user::{
+        UnixGroupCreateBody, UnixGroupRecords, UnixUserCreateBody, UnixUserLookup,
+        UnixUserLookupBody, UnixUserRecords,
+    },
     volume::{
-        Volume, VolumeMetricRecords, VolumeMoveAggregate, VolumeMoveBody, VolumeMoveMovement,
-        VolumeRecords, VolumeResizeBody,
+        Volume, VolumeClone, VolumeCloneCreateBody, VolumeCloneCreateClone, VolumeCloneOnly,
+        VolumeCloneSplit, VolumeCloneSplitBody, VolumeMetricRecords, VolumeMoveAggregate,
+        VolumeMoveBody, VolumeMoveMovement, VolumeRecords, VolumeResizeBody,
     },
-    AggregatedMetrics, Cluster, Duration, Metrics, MetricsRecord, OntapApiError, Status, Version, s3::S3BucketRecords,
+    AggregatedMetrics, CliOutput, Cluster, ClusterModifyBody, Duration, Metrics, MetricsQuery,
+    MetricsRecord, MetricsRecords, MetricsTimeSeries, NameOnly, NfsMetricsRecords, OntapApiError,
+    StatisticsRecords, Status, SvmProtocol, UuidOnly, Version,
 };
-use tracing::instrument;
+use std::{sync::Arc, time::Instant};
+use tracing::{debug, field, info_span, instrument, Instrument, Span};
 
+pub mod aggregation;
+#[cfg(feature = "exporter")]
+pub mod exporter;
+pub mod health;
 pub mod models;
+pub mod sampler;
+pub mod telemetry;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+const METRICS_FIELDS: &str = "duration,iops,latency,throughput,status,timestamp";
+const THROUGHPUT_METRICS_FIELDS: &str = "duration,throughput,status,timestamp";
+const NODE_METRICS_FIELDS: &str = "duration,processor_utilization,status,timestamp";
+const IP_INTERFACE_FIELDS: &str =
+    "enabled,ip,ipspace,location,name,scope,service_policy,services,state,svm,uuid,vip";
+const SHELF_FIELDS: &str =
+    "bays,connection_type,disk_count,drawers,id,uid,model,name,paths,ports,state,acps,current_sensors,fans,frus,temperature_sensors,voltage_sensors";
+const SENSOR_FIELDS: &str =
+    "node,index,name,type,value,value_units,threshold_state,critical_low_threshold,warning_low_threshold,warning_high_threshold,critical_high_threshold,discrete_state,discrete_value";
+const DISK_FIELDS: &str =
+    "aggregates,bay,class,container_type,dr_node,fips_certified,firmware_version,home_node,model,name,node,pool,protection_mode,rated_life_used_percent,rpm,self_encrypting,serial_number,state,type,uid,usable_size,vendor";
+const VOLUME_CLONE_FIELDS: &str =
+    "clone.is_flexclone,clone.parent_volume,clone.parent_svm,clone.parent_snapshot,clone.split_complete_percent,clone.split_estimate,clone.split_initiated";
+const FLEXCACHE_FIELDS: &str =
+    "uuid,name,svm,origins,aggregates,size,path,dr_cache,global_file_locking_enabled";
+const NFS_CLIENT_FIELDS: &str =
+    "client_ip,idle_duration,local_request_count,node,protocol,remote_request_count,server_ip,svm,volume";
+const LDAP_FIELDS: &str =
+    "svm,servers,ad_domain,preferred_ad_servers,base_dn,base_scope,bind_dn,schema,port,use_start_tls,session_security";
+const NODE_FIELDS: &str =
+    "state,model,serial_number,uptime,service_processor,ha,version,location,system_id,vendor_serial_number,management_interfaces,controller";
+const CLUSTER_FIELDS: &str =
+    "name,uuid,location,contact,version,management_interfaces,dns_domains,name_servers,ntp_servers,timezone,certificate,peering_policy,metric,statistics";
 
 pub enum ApiVersion {
     V9,
@@ -57,10 +147,13 @@ impl TryFrom<Version> for ApiVersion {
     }
 }
 
+#[allow(clippy::match_single_binding)]
 impl ApiVersion {
     pub fn volume_fields(&self) -> &str {
         match self {
-            _ => "size,svm,aggregates,space,clone,autosize,files,movement,encryption,efficiency",
+            _ => {
+                "size,svm,aggregates,space,clone,autosize,files,movement,encryption,efficiency,qos"
+            }
         }
     }
 
@@ -77,7 +170,6 @@ impl ApiVersion {
             _ => "svm,size,volume,encryption,size",
         }
     }
-
 }
 
 pub struct OntapConnectionParams {
@@ -86,107 +178,3035 @@ pub struct OntapConnectionParams {
     pub password: String,
 }
 
+/// Options applying to every request of an `OntapClient`, including the version lookup of
+/// `OntapConnectionParams::connect_with_options`.
+#[derive(Default, Clone)]
+pub struct OntapClientOptions {
+    /// See `OntapClient::with_max_retries`.
+    pub max_retries: u32,
+    /// See `OntapClient::with_observer`.
+    pub observer: Option<Arc<dyn RequestObserver>>,
+}
+
 impl OntapConnectionParams {
     pub async fn connect(self) -> Result<OntapClient, OntapApiError> {
+        self.connect_with_options(OntapClientOptions::default())
+            .await
+    }
+
+    pub async fn connect_with_options(
+        self,
+        options: OntapClientOptions,
+    ) -> Result<OntapClient, OntapApiError> {
         let reqwest_client = ClientBuilder::new()
             .danger_accept_invalid_certs(true)
             .build()?;
 
-        let url = format!("{}/cluster", self.url);
-        let res = reqwest_client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .send()
+        let mut client = OntapClient {
+            url: self.url,
+            username: self.username,
+            password: self.password,
+            client: reqwest_client,
+            // replaced below once the cluster reported its version
+            api_version: ApiVersion::V9,
+            max_retries: options.max_retries,
+            observer: options.observer,
+        };
+        let url = format!("{}/cluster", client.url);
+        let res = client
+            .execute(
+                client
+                    .client
+                    .get(url)
+                    .basic_auth(&client.username, Some(&client.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let cluster: Cluster = res.json().await?;
+        client.api_version = ApiVersion::try_from(cluster.version)?;
+        Ok(client)
+    }
+}
+
+pub struct OntapClient {
+    url: Url,
+    username: String,
+    password: String,
+    client: reqwest::Client,
+    api_version: ApiVersion,
+    max_retries: u32,
+    observer: Option<Arc<dyn RequestObserver>>,
+}
+
+#[derive(Deserialize)]
+struct OntapErrorResponse {
+    error: OntapError,
+}
+
+#[derive(Deserialize)]
+struct OntapError {
+    code: Option<String>,
+}
+
+/// Response returned by `OntapClient::execute`. The body of a failed request has already been
+/// read to find the ONTAP error code.
+struct ApiResponse {
+    status: StatusCode,
+    body: ApiResponseBody,
+}
+
+enum ApiResponseBody {
+    Unread(Response),
+    Error(String),
+}
+
+impl ApiResponse {
+    async fn read(res: Response) -> Result<(Self, Option<String>), reqwest::Error> {
+        let status = res.status();
+        if status.is_success() {
+            let body = ApiResponseBody::Unread(res);
+            return Ok((Self { status, body }, None));
+        }
+        let text = res.text().await?;
+        let code = serde_json::from_str::<OntapErrorResponse>(&text)
+            .ok()
+            .and_then(|e| e.error.code);
+        let body = ApiResponseBody::Error(text);
+        Ok((Self { status, body }, code))
+    }
+
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    async fn text(self) -> Result<String, reqwest::Error> {
+        match self.body {
+            ApiResponseBody::Unread(res) => res.text().await,
+            ApiResponseBody::Error(text) => Ok(text),
+        }
+    }
+
+    /// The job started by a request, an empty body means the request completed synchronously.
+    async fn job(self) -> Result<JobResponse, OntapApiError> {
+        let text = self.text().await?;
+        if text.trim().is_empty() {
+            return Ok(JobResponse { job: None });
+        }
+        serde_json::from_str(&text).map_err(|e| OntapApiError::UnexpectedResponse(e.to_string()))
+    }
+
+    async fn json<T: DeserializeOwned>(self) -> Result<T, ApiBodyError> {
+        match self.body {
+            ApiResponseBody::Unread(res) => Ok(res.json().await?),
+            ApiResponseBody::Error(text) => serde_json::from_str(&text)
+                .map_err(|_| ApiBodyError::Status(self.status.as_u16(), text)),
+        }
+    }
+}
+
+enum ApiBodyError {
+    Request(reqwest::Error),
+    /// An error body that did not match the expected type.
+    Status(u16, String),
+}
+
+impl From<reqwest::Error> for ApiBodyError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Request(e)
+    }
+}
+
+impl From<ApiBodyError> for OntapApiError {
+    fn from(e: ApiBodyError) -> Self {
+        match e {
+            ApiBodyError::Request(e) => Self::RequestError(e),
+            ApiBodyError::Status(status, text) => Self::HttpStatusCodeWithError(status, text),
+        }
+    }
+}
+
+impl OntapClient {
+    /// Retries idempotent requests failing with a connection error, a timeout or a 502, 503
+    /// or 504 status up to `max_retries` times, with exponential backoff.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Reports every API call to `observer`, e.g. a `telemetry::RequestMetrics`.
+    pub fn with_observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sends a request inside an `ontap_request` span recording method, path, status, ONTAP
+    /// error code, retries and latency.
+    async fn execute(&self, request: RequestBuilder) -> Result<ApiResponse, OntapApiError> {
+        let request = request.build()?;
+        let method = request.method().clone();
+        let path = request.url().path().to_string();
+        let span = info_span!(
+            "ontap_request",
+            method = %method,
+            path = %path,
+            status = field::Empty,
+            ontap_error_code = field::Empty,
+            retries = field::Empty,
+            latency_ms = field::Empty,
+        );
+        async {
+            let started = Instant::now();
+            let (result, retries) = self.execute_with_retries(request).await;
+            let (result, ontap_error_code) = match result {
+                Ok(res) => match ApiResponse::read(res).await {
+                    Ok((res, code)) => (Ok(res), code),
+                    Err(e) => (Err(e), None),
+                },
+                Err(e) => (Err(e), None),
+            };
+            let latency = started.elapsed();
+            let status = result.as_ref().ok().map(|r| r.status().as_u16());
+
+            let span = Span::current();
+            if let Some(status) = status {
+                span.record("status", status);
+            }
+            if let Some(code) = &ontap_error_code {
+                span.record("ontap_error_code", code.as_str());
+            }
+            span.record("retries", retries);
+            span.record("latency_ms", latency.as_millis() as u64);
+            debug!("ONTAP request finished");
+
+            if let Some(observer) = &self.observer {
+                observer.observe(&RequestEvent {
+                    method: &method,
+                    path: &path,
+                    status,
+                    ontap_error_code: ontap_error_code.as_deref(),
+                    retries,
+                    latency,
+                });
+            }
+            Ok(result?)
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn execute_with_retries(
+        &self,
+        request: reqwest::Request,
+    ) -> (Result<Response, reqwest::Error>, u32) {
+        let idempotent = request.method() == Method::GET || request.method() == Method::HEAD;
+        let mut request = request;
+        let mut retries = 0;
+        loop {
+            let next = if idempotent && retries < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            let result = self.client.execute(request).await;
+            let retryable = match &result {
+                Ok(res) => matches!(res.status().as_u16(), 502..=504),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            match next {
+                Some(next) if retryable => {
+                    tokio::time::sleep(retry_delay(retries)).await;
+                    retries += 1;
+                    request = next;
+                }
+                _ => return (result, retries),
+            }
+        }
+    }
+
+    pub async fn get_volumes(&self) -> Result<VolumeRecords, OntapApiError> {
+        let url = format!("{}/storage/volumes", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", self.api_version.volume_fields())]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_ip_interfaces(&self) -> Result<IpInterfaceRecords, OntapApiError> {
+        let url = format!("{}/network/ip/interfaces", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", IP_INTERFACE_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_ethernet_ports(&self) -> Result<PortRecords, OntapApiError> {
+        let url = format!("{}/network/ethernet/ports", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", self.api_version.ethernet_port_fields())]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_ip_interface_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/network/ip/interfaces/{}/metrics", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(THROUGHPUT_METRICS_FIELDS)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_ethernet_port_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/network/ethernet/ports/{}/metrics", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(THROUGHPUT_METRICS_FIELDS)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    pub async fn ip_interface_set_admin_status(
+        &self,
+        uuid: &str,
+        enabled: bool,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ip/interfaces/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&IpInterfaceAdminState { enabled })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn ip_interface_revert(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ip/interfaces/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&IpInterfaceHomeState {
+                        location: IpInterfaceHomeStateLocation { is_home: true },
+                    })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn create_vlan_port(
+        &self,
+        node_name: &str,
+        base_port_uuid: &str,
+        tag: u32,
+        broadcast_domain: Option<PortBroadcastDomainRef>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/ports", self.url);
+        let body = PortCreateBody {
+            typ: PortType::Vlan,
+            node: NameOnly {
+                name: node_name.into(),
+            },
+            broadcast_domain,
+            vlan: Some(PortCreateVlan {
+                base_port: UuidOnly {
+                    uuid: base_port_uuid.into(),
+                },
+                tag,
+            }),
+            lag: None,
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn create_lag_port(
+        &self,
+        node_name: &str,
+        member_port_uuids: &[&str],
+        mode: PortLagMode,
+        distribution_policy: PortLagDistributionPolicy,
+        broadcast_domain: Option<PortBroadcastDomainRef>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/ports", self.url);
+        let body = PortCreateBody {
+            typ: PortType::Lag,
+            node: NameOnly {
+                name: node_name.into(),
+            },
+            broadcast_domain,
+            vlan: None,
+            lag: Some(PortCreateLag {
+                mode,
+                distribution_policy,
+                member_ports: member_port_uuids
+                    .iter()
+                    .map(|uuid| UuidOnly {
+                        uuid: (*uuid).into(),
+                    })
+                    .collect(),
+            }),
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_ethernet_port(
+        &self,
+        uuid: &str,
+        body: &PortModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/ports/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_ethernet_port(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/ports/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_ipspaces(&self) -> Result<IpspaceRecords, OntapApiError> {
+        let url = format!("{}/network/ipspaces", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_ipspace(&self, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ipspaces", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&IpspaceBody { name: name.into() })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn rename_ipspace(&self, uuid: &str, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ipspaces/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&IpspaceBody { name: name.into() })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_ipspace(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ipspaces/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_broadcast_domains(&self) -> Result<BroadcastDomainRecords, OntapApiError> {
+        let url = format!("{}/network/ethernet/broadcast-domains", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name,ipspace,mtu,ports")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_broadcast_domain(
+        &self,
+        name: &str,
+        ipspace_name: &str,
+        mtu: u32,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/broadcast-domains", self.url);
+        let body = BroadcastDomainCreateBody {
+            name: name.into(),
+            ipspace: NameOnly {
+                name: ipspace_name.into(),
+            },
+            mtu,
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_broadcast_domain(
+        &self,
+        uuid: &str,
+        body: &BroadcastDomainModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/broadcast-domains/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_broadcast_domain(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ethernet/broadcast-domains/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_network_routes(
+        &self,
+        svm_name: Option<&str>,
+        ipspace_name: Option<&str>,
+    ) -> Result<NetworkRouteRecords, OntapApiError> {
+        let url = format!("{}/network/ip/routes", self.url);
+        let mut query = vec![("fields", "destination,gateway,ipspace,metric,scope,svm")];
+        if let Some(svm_name) = svm_name {
+            query.push(("svm.name", svm_name));
+        }
+        if let Some(ipspace_name) = ipspace_name {
+            query.push(("ipspace.name", ipspace_name));
+        }
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_network_route(
+        &self,
+        body: &NetworkRouteCreateBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ip/routes", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_network_route(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/network/ip/routes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_dns(&self) -> Result<DnsRecords, OntapApiError> {
+        let url = format!("{}/name-services/dns", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "svm,domains,servers,scope")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_dns(
+        &self,
+        svm_name: &str,
+        domains: Vec<String>,
+        servers: Vec<String>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/dns", self.url);
+        let body = DnsCreateBody {
+            svm: NameOnly {
+                name: svm_name.into(),
+            },
+            domains,
+            servers,
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_dns(
+        &self,
+        svm_uuid: &str,
+        domains: Vec<String>,
+        servers: Vec<String>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/dns/{svm_uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&DnsModifyBody { domains, servers })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_dns(&self, svm_uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/dns/{svm_uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_ntp_servers(&self) -> Result<NtpServerRecords, OntapApiError> {
+        let url = format!("{}/cluster/ntp/servers", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "server,version,authentication_enabled,key")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_ntp_server(
+        &self,
+        server: &str,
+        version: Option<NtpVersion>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/cluster/ntp/servers", self.url);
+        let body = NtpServerCreateBody {
+            server: server.into(),
+            version,
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_ntp_server(&self, server: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/cluster/ntp/servers/{server}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_shelves(&self) -> Result<ShelfRecords, OntapApiError> {
+        let url = format!("{}/storage/shelves", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", SHELF_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_sensors(&self) -> Result<SensorRecords, OntapApiError> {
+        let url = format!("{}/cluster/sensors", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", SENSOR_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_chassis(&self) -> Result<ChassisRecords, OntapApiError> {
+        let url = format!("{}/cluster/chassis", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "id,state,nodes,frus")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_s3_buckets(&self) -> Result<S3BucketRecords, OntapApiError> {
+        let url = format!("{}/protocols/s3/buckets", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", self.api_version.bucket_fields())]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_disks(&self) -> Result<DiskRecords, OntapApiError> {
+        let url = format!("{}/storage/disks", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", DISK_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn assign_disk(
+        &self,
+        name: &str,
+        node_name: &str,
+        pool: Option<&str>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/disks", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&DiskOwnershipBody::assign(node_name, pool))
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("name", name)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn unassign_disk(&self, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/disks", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&DiskOwnershipBody::unassign())
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("name", name)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn fail_disk(&self, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/disks", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&DiskStateBody {
+                        state: DiskState::Broken,
+                    })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("name", name)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn unfail_disk(&self, name: &str, make_spare: bool) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/disks", self.url);
+        let state = if make_spare {
+            DiskState::Spare
+        } else {
+            DiskState::Present
+        };
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&DiskStateBody { state })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("name", name)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn sanitize_disk(&self, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/disks", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&DiskEncryptionOperationBody {
+                        encryption_operation: DiskEncryptionOperation::SanitizeDisk,
+                    })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("name", name)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn resize_volume(&self, uuid: &str, new_size: u64) -> Result<(), OntapApiError> {
+        let url = format!("{url}/storage/volumes/{uuid}", url = self.url, uuid = uuid);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&VolumeResizeBody { size: new_size })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(())
+    }
+
+    /// Like `resize_volume`, but returns the job ONTAP started for the resize.
+    pub async fn resize_volume_job(
+        &self,
+        uuid: &str,
+        new_size: u64,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{url}/storage/volumes/{uuid}", url = self.url, uuid = uuid);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&VolumeResizeBody { size: new_size })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        res.job().await
+    }
+
+    pub async fn move_volume(
+        &self,
+        uuid: &str,
+        destination_aggregate_uuid: &str,
+        cutover_window: u32,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{url}/storage/volumes/{uuid}", url = self.url, uuid = uuid);
+        let body = VolumeMoveBody {
+            movement: VolumeMoveMovement {
+                cutover_window,
+                destination_aggregate: VolumeMoveAggregate {
+                    uuid: destination_aggregate_uuid.into(),
+                },
+            },
+        };
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(())
+    }
+
+    /// Like `move_volume`, but returns the job ONTAP started for the move.
+    pub async fn move_volume_job(
+        &self,
+        uuid: &str,
+        destination_aggregate_uuid: &str,
+        cutover_window: u32,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{url}/storage/volumes/{uuid}", url = self.url, uuid = uuid);
+        let body = VolumeMoveBody {
+            movement: VolumeMoveMovement {
+                cutover_window,
+                destination_aggregate: VolumeMoveAggregate {
+                    uuid: destination_aggregate_uuid.into(),
+                },
+            },
+        };
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        res.job().await
+    }
+
+    pub async fn get_volume(&self, uuid: &str) -> Result<Volume, OntapApiError> {
+        let url = format!("{}/storage/volumes/{}", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", self.api_version.volume_fields())]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn clone_volume(
+        &self,
+        parent: &Volume,
+        snapshot: Option<&str>,
+        name: &str,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/volumes", self.url);
+        let body = VolumeCloneCreateBody {
+            name: name.into(),
+            svm: NameOnly {
+                name: parent.svm.name.clone(),
+            },
+            clone: VolumeCloneCreateClone {
+                is_flexclone: true,
+                parent_volume: NameOnly {
+                    name: parent.name.clone(),
+                },
+                parent_snapshot: snapshot.map(|name| NameOnly { name: name.into() }),
+            },
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_volume_clone(&self, uuid: &str) -> Result<VolumeClone, OntapApiError> {
+        let url = format!("{}/storage/volumes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", VOLUME_CLONE_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let volume: VolumeCloneOnly = res.json().await?;
+        Ok(volume.clone)
+    }
+
+    pub async fn start_volume_clone_split(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/volumes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&VolumeCloneSplitBody {
+                        clone: VolumeCloneSplit {
+                            split_initiated: true,
+                        },
+                    })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn stop_volume_clone_split(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/volumes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&VolumeCloneSplitBody {
+                        clone: VolumeCloneSplit {
+                            split_initiated: false,
+                        },
+                    })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_flexcaches(&self) -> Result<FlexcacheRecords, OntapApiError> {
+        let url = format!("{}/storage/flexcache/flexcaches", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", FLEXCACHE_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_flexcache(
+        &self,
+        body: &FlexcacheCreateBody,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/flexcache/flexcaches", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn delete_flexcache(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/flexcache/flexcaches/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_flexcache_origins(&self) -> Result<FlexcacheOriginRecords, OntapApiError> {
+        let url = format!("{}/storage/flexcache/origins", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[(
+                        "fields",
+                        "uuid,name,svm,flexcaches,global_file_locking_enabled",
+                    )]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_qos_policies(&self) -> Result<QosPolicyRecords, OntapApiError> {
+        let url = format!("{}/storage/qos/policies", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name,svm,pgid,object_count,fixed,adaptive")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_qos_policy(&self, body: &QosPolicyCreateBody) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/qos/policies", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_qos_policy(
+        &self,
+        uuid: &str,
+        body: &QosPolicyModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/qos/policies/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_qos_policy(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/qos/policies/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn set_volume_qos_policy(
+        &self,
+        uuid: &str,
+        policy_name: &str,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/volumes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&VolumeQosBody {
+                        qos: VolumeQosPolicy {
+                            policy: QosPolicyRef {
+                                name: policy_name.into(),
+                                uuid: None,
+                            },
+                        },
+                    })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn set_lun_qos_policy(
+        &self,
+        uuid: &str,
+        policy_name: &str,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/san/luns/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&LunQosBody {
+                        qos_policy: QosPolicyRef {
+                            name: policy_name.into(),
+                            uuid: None,
+                        },
+                    })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_qos_workloads(
+        &self,
+        policy_name: Option<&str>,
+    ) -> Result<QosWorkloadRecords, OntapApiError> {
+        let url = format!("{}/storage/qos/workloads", self.url);
+        let mut query = vec![(
+            "fields",
+            "uuid,name,svm,policy,workload_class,wid,volume,lun,file,qtree",
+        )];
+        if let Some(policy_name) = policy_name {
+            query.push(("policy.name", policy_name));
+        }
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_volume_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/storage/volumes/{}/metrics", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(METRICS_FIELDS)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let metrics: VolumeMetricRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    pub async fn get_volume_statistics(&self) -> Result<StatisticsRecords, OntapApiError> {
+        let url = format!("{}/storage/volumes", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name,statistics")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    /// Fetches the raw counters and feeds them to `sampler`, returning the rates of all
+    /// objects that had a usable previous sample, keyed by uuid.
+    pub async fn sample_volume_statistics(
+        &self,
+        sampler: &mut StatisticsSampler,
+    ) -> Result<Vec<(String, StatisticsRates)>, OntapApiError> {
+        let fetched_at = chrono::Utc::now();
+        let records = self.get_volume_statistics().await?.records;
+        Ok(records
+            .iter()
+            .filter_map(|r| {
+                let statistics = r.statistics.as_ref()?;
+                let rates = sampler.record(&r.uuid, statistics, fetched_at)?;
+                Some((r.uuid.clone(), rates))
+            })
+            .collect())
+    }
+
+    pub async fn get_volume_snapshots(&self, uuid: &str) -> Result<SnapshotRecords, OntapApiError> {
+        let url = format!("{}/storage/volumes/{}/snapshots", self.url, uuid);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name,owners,state")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await.unwrap(),
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_aggregates(&self) -> Result<AggregateRecords, OntapApiError> {
+        let url = format!("{}/storage/aggregates", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "node,space,block_storage")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_aggregate_statistics(&self) -> Result<StatisticsRecords, OntapApiError> {
+        let url = format!("{}/storage/aggregates", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name,statistics")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    /// Fetches the raw counters and feeds them to `sampler`, returning the rates of all
+    /// objects that had a usable previous sample, keyed by uuid.
+    pub async fn sample_aggregate_statistics(
+        &self,
+        sampler: &mut StatisticsSampler,
+    ) -> Result<Vec<(String, StatisticsRates)>, OntapApiError> {
+        let fetched_at = chrono::Utc::now();
+        let records = self.get_aggregate_statistics().await?.records;
+        Ok(records
+            .iter()
+            .filter_map(|r| {
+                let statistics = r.statistics.as_ref()?;
+                let rates = sampler.record(&r.uuid, statistics, fetched_at)?;
+                Some((r.uuid.clone(), rates))
+            })
+            .collect())
+    }
+
+    pub async fn get_aggregate_recommendations(
+        &self,
+    ) -> Result<AggregateRecommendationRecords, OntapApiError> {
+        let url = format!("{}/storage/aggregates", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "name,node,block_storage"), ("recommend", "true")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_aggregate(
+        &self,
+        name: &str,
+        node_name: &str,
+        primary: AggregatePrimaryBlockStorage,
+        mirror: bool,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/aggregates", self.url);
+        let body = AggregateCreateBody {
+            name: name.into(),
+            node: NameOnly {
+                name: node_name.into(),
+            },
+            block_storage: AggregateCreateBlockStorage {
+                primary,
+                mirror: mirror.then_some(AggregateMirror { enabled: true }),
+            },
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    /// Grows the aggregate to `total_disk_count` data disks. ONTAP takes the new total, not the
+    /// number of disks to add, so a value below the current `block_storage.primary.disk_count`
+    /// is rejected.
+    pub async fn expand_aggregate(
+        &self,
+        uuid: &str,
+        total_disk_count: u32,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/aggregates/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&AggregateExpandBody {
+                        block_storage: AggregateExpandBlockStorage {
+                            primary: AggregateExpandPrimary {
+                                disk_count: total_disk_count,
+                            },
+                        },
+                    })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn rename_aggregate(&self, uuid: &str, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/storage/aggregates/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&AggregateRenameBody { name: name.into() })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_aggregate(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/storage/aggregates/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_nfs_clients(&self) -> Result<NfsClientRecords, OntapApiError> {
+        let url = format!("{}/protocols/nfs/connected-clients", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", NFS_CLIENT_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_plexes(&self, aggregate_uuid: &str) -> Result<PlexRecords, OntapApiError> {
+        let url = format!("{}/storage/aggregates/{aggregate_uuid}/plexes", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[(
+                        "fields",
+                        "aggregate,name,online,pool,raid_groups,resync,state",
+                    )]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn assess_health(
+        &self,
+        thresholds: &HealthThresholds,
+    ) -> Result<Vec<Finding>, OntapApiError> {
+        let disks = self.get_disks().await?;
+        let shelves = self.get_shelves().await?;
+        let mut plexes = vec![];
+        for aggregate in self.get_aggregates().await?.records {
+            plexes.extend(self.get_plexes(&aggregate.uuid).await?.records);
+        }
+        let mut findings = health::assess(&disks.records, &shelves.records, &plexes, thresholds);
+        findings.extend(health::assess_sensors(&self.get_sensors().await?.records));
+        findings.extend(health::assess_chassis(&self.get_chassis().await?.records));
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity()));
+        Ok(findings)
+    }
+
+    pub async fn get_aggregate_raid_groups(
+        &self,
+        aggregate_uuid: &str,
+    ) -> Result<Vec<RaidGroup>, OntapApiError> {
+        let plexes = self.get_plexes(aggregate_uuid).await?;
+        Ok(plexes
+            .records
+            .into_iter()
+            .flat_map(|p| p.raid_groups)
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_aggregate_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/storage/aggregates/{}/metrics", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(METRICS_FIELDS)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let metrics: AggregateMetricRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_lun_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/storage/luns/{}/metrics", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(METRICS_FIELDS)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_nvme_namespace_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/storage/namespaces/{}/metrics", self.url, uuid);
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(METRICS_FIELDS)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    pub async fn get_jobs(&self) -> Result<JobRecords, OntapApiError> {
+        let url = format!("{}/cluster/jobs", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[(
+                        "fields",
+                        "state,message,description,code,start_time,end_time",
+                    )]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_job(&self, uuid: &str) -> Result<Job, OntapApiError> {
+        let url = format!("{}/cluster/jobs/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[(
+                        "fields",
+                        "state,message,description,code,start_time,end_time",
+                    )]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_svms(&self) -> Result<SvmRecords, OntapApiError> {
+        let url = format!("{}/svm/svms", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "aggregates,state,cifs,ldap,nfs,nis,nsswitch")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn get_svm(&self, uuid: &str) -> Result<Svm, OntapApiError> {
+        let url = format!("{}/svm/svms/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "aggregates,state,cifs,ldap,nfs,nis,nsswitch")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_svm_protocol_metrics(
+        &self,
+        svm_uuid: &str,
+        protocol: SvmProtocol,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!(
+            "{}/{}/services/{}/metrics",
+            self.url,
+            protocol.path(),
+            svm_uuid
+        );
+        let query = match protocol.nfs_version() {
+            Some(version) => query
+                .to_query(&format!("{version},timestamp"))
+                .into_iter()
+                .map(|(key, value)| match key {
+                    "duration" => (format!("{version}.duration"), value),
+                    _ => (key.to_string(), value),
+                })
+                .collect::<Vec<_>>(),
+            None => query
+                .to_query(METRICS_FIELDS)
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        };
+
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        if protocol.nfs_version().is_some() {
+            let metrics: NfsMetricsRecords = res.json().await?;
+            return Ok(MetricsTimeSeries::new(
+                metrics
+                    .records
+                    .iter()
+                    .filter_map(|r| r.version(protocol))
+                    .collect(),
+            ));
+        }
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    pub async fn get_ldap(&self) -> Result<LdapRecords, OntapApiError> {
+        let url = format!("{}/name-services/ldap", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", LDAP_FIELDS)]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_ldap(&self, body: &LdapBody) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/ldap", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_ldap(&self, svm_uuid: &str, body: &LdapBody) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/ldap/{svm_uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_ldap(&self, svm_uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/ldap/{svm_uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_nis(&self) -> Result<NisRecords, OntapApiError> {
+        let url = format!("{}/name-services/nis", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "svm,domain,servers,bound_servers")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_nis(
+        &self,
+        svm_name: &str,
+        domain: &str,
+        servers: Vec<String>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/nis", self.url);
+        let body = NisCreateBody {
+            svm: NameOnly {
+                name: svm_name.into(),
+            },
+            domain: domain.into(),
+            servers,
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_nis(
+        &self,
+        svm_uuid: &str,
+        body: &NisModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/nis/{svm_uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_nis(&self, svm_uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/nis/{svm_uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_local_hosts(&self) -> Result<LocalHostRecords, OntapApiError> {
+        let url = format!("{}/name-services/local-hosts", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "owner,address,hostname,aliases")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_local_host(&self, body: &LocalHostCreateBody) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/local-hosts", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_local_host(
+        &self,
+        owner_uuid: &str,
+        address: &str,
+    ) -> Result<(), OntapApiError> {
+        let url = format!(
+            "{}/name-services/local-hosts/{owner_uuid}/{address}",
+            self.url
+        );
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_unix_users(
+        &self,
+        svm_uuid: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<UnixUserRecords, OntapApiError> {
+        let url = format!("{}/name-services/unix-users", self.url);
+        let mut query = vec![("fields", "svm,name,id,primary_gid,full_name")];
+        if let Some(svm_uuid) = svm_uuid {
+            query.push(("svm.uuid", svm_uuid));
+        }
+        if let Some(name) = name {
+            query.push(("name", name));
+        }
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_unix_user(&self, body: &UnixUserCreateBody) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/unix-users", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_unix_user(&self, svm_uuid: &str, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/unix-users/{svm_uuid}/{name}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_unix_groups(
+        &self,
+        svm_uuid: Option<&str>,
+    ) -> Result<UnixGroupRecords, OntapApiError> {
+        let url = format!("{}/name-services/unix-groups", self.url);
+        let mut query = vec![("fields", "svm,name,id,users")];
+        if let Some(svm_uuid) = svm_uuid {
+            query.push(("svm.uuid", svm_uuid));
+        }
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_unix_group(&self, body: &UnixGroupCreateBody) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/unix-groups", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn add_unix_group_user(
+        &self,
+        svm_uuid: &str,
+        group: &str,
+        user: &str,
+    ) -> Result<(), OntapApiError> {
+        let url = format!(
+            "{}/name-services/unix-groups/{svm_uuid}/{group}/users",
+            self.url
+        );
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&NameOnly { name: user.into() })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_unix_group(&self, svm_uuid: &str, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/unix-groups/{svm_uuid}/{name}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_name_mappings(
+        &self,
+        svm_uuid: Option<&str>,
+    ) -> Result<NameMappingRecords, OntapApiError> {
+        let url = format!("{}/name-services/name-mappings", self.url);
+        let mut query = vec![(
+            "fields",
+            "svm,direction,index,pattern,replacement,client_match",
+        )];
+        if let Some(svm_uuid) = svm_uuid {
+            query.push(("svm.uuid", svm_uuid));
+        }
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_name_mapping(
+        &self,
+        body: &NameMappingCreateBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/name-services/name-mappings", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_name_mapping(
+        &self,
+        svm_uuid: &str,
+        direction: NameMappingDirection,
+        index: u32,
+    ) -> Result<(), OntapApiError> {
+        let direction = direction.as_str();
+        let url = format!(
+            "{}/name-services/name-mappings/{svm_uuid}/{direction}/{index}",
+            self.url
+        );
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Resolves a UNIX user through the `passwd` sources configured in the SVM's nsswitch, in
+    /// order. Local users are looked up through the REST API; the first remote source (LDAP, NIS)
+    /// hands the lookup over to ONTAP's own resolver, which also reports the source that answered.
+    /// Returns `None` when no source knows the user.
+    pub async fn lookup_unix_user(
+        &self,
+        svm_uuid: &str,
+        name: &str,
+    ) -> Result<Option<UnixUserLookup>, OntapApiError> {
+        let svm = self.get_svm(svm_uuid).await?;
+        let sources = svm
+            .nsswitch
+            .and_then(|n| n.passwd)
+            .unwrap_or_else(|| vec![NsswitchSource::Files]);
+        for source in sources {
+            if source == NsswitchSource::Files {
+                let users = self.get_unix_users(Some(svm_uuid), Some(name)).await?;
+                if let Some(user) = users.records.into_iter().next() {
+                    return Ok(Some(UnixUserLookup {
+                        name: user.name,
+                        uid: user.id,
+                        gid: user.primary_gid,
+                        source: Some(NsswitchSource::Files),
+                    }));
+                }
+                continue;
+            }
+
+            let url = format!(
+                "{}/private/cli/vserver/services/name-service/getxxbyyy/getpwbyname",
+                self.url
+            );
+            let body = UnixUserLookupBody {
+                vserver: svm.name.clone(),
+                username: name.into(),
+                show_source: true,
+            };
+            let res = self
+                .execute(
+                    self.client
+                        .post(url)
+                        .json(&body)
+                        .basic_auth(&self.username, Some(&self.password))
+                        .header("accept", "application/json"),
+                )
+                .await?;
+            if !res.status().is_success() {
+                let status = res.status().as_u16();
+                let error = res.text().await?;
+                if UnixUserLookup::is_not_found(&error) {
+                    return Ok(None);
+                }
+                return Err(OntapApiError::HttpStatusCodeWithError(status, error));
+            }
+            let output: CliOutput = res.json().await?;
+            return Ok(UnixUserLookup::from_cli_output(&output.cli_output));
+        }
+        Ok(None)
+    }
+
+    pub async fn get_cluster_peers(&self) -> Result<ClusterPeerRecords, OntapApiError> {
+        let url = format!("{}/cluster/peers", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[(
+                        "fields",
+                        "uuid,name,authentication,encryption,ipspace,remote,status,version",
+                    )]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_cluster_peer(
+        &self,
+        body: &ClusterPeerCreateBody,
+    ) -> Result<ClusterPeerSetupRecords, OntapApiError> {
+        let url = format!("{}/cluster/peers", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn modify_cluster_peer(
+        &self,
+        uuid: &str,
+        body: &ClusterPeerModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/cluster/peers/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_cluster_peer(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/cluster/peers/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_intercluster_addresses(&self) -> Result<Vec<String>, OntapApiError> {
+        let url = format!("{}/network/ip/interfaces", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[
+                        ("fields", IP_INTERFACE_FIELDS),
+                        ("services", "intercluster_core"),
+                    ]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let interfaces: IpInterfaceRecords = res.json().await?;
+        Ok(interfaces
+            .records
+            .into_iter()
+            .map(|i| i.ip.address)
+            .collect())
+    }
+
+    /// Establishes a cluster peer relationship between this cluster and `remote`.
+    ///
+    /// A passphrase is generated on this cluster, which is then used to create the matching peer
+    /// on the remote side. Both sides are pointed at the other's intercluster addresses. If the
+    /// remote side cannot be created, the local peer is deleted again.
+    pub async fn peer_cluster(
+        &self,
+        remote: &OntapClient,
+    ) -> Result<ClusterPeerSetup, OntapApiError> {
+        let local_addresses = self.get_intercluster_addresses().await?;
+        let remote_addresses = remote.get_intercluster_addresses().await?;
+
+        let setup = self
+            .create_cluster_peer(&ClusterPeerCreateBody {
+                remote: ClusterPeerCreateRemote {
+                    ip_addresses: remote_addresses,
+                },
+                authentication: ClusterPeerCreateAuthentication {
+                    generate_passphrase: Some(true),
+                    ..Default::default()
+                },
+                name: None,
+                ipspace: None,
+            })
+            .await?
+            .records
+            .into_iter()
+            .next()
+            .ok_or(OntapApiError::UnexpectedResponse(
+                "cluster peer creation returned no records".into(),
+            ))?;
+        let passphrase = setup
+            .authentication
+            .as_ref()
+            .and_then(|a| a.passphrase.clone())
+            .ok_or(OntapApiError::UnexpectedResponse(
+                "cluster peer creation returned no passphrase".into(),
+            ))?;
+
+        let accepted = remote
+            .create_cluster_peer(&ClusterPeerCreateBody {
+                remote: ClusterPeerCreateRemote {
+                    ip_addresses: local_addresses,
+                },
+                authentication: ClusterPeerCreateAuthentication {
+                    passphrase: Some(passphrase),
+                    ..Default::default()
+                },
+                name: None,
+                ipspace: None,
+            })
+            .await;
+        if let Err(e) = accepted {
+            let local = self
+                .get_cluster_peers()
+                .await?
+                .records
+                .into_iter()
+                .find(|p| setup.name.as_deref() == Some(p.name.as_str()));
+            if let Some(peer) = local {
+                self.delete_cluster_peer(&peer.uuid).await?;
+            }
+            return Err(e);
+        }
+
+        Ok(setup)
+    }
+
+    pub async fn get_svm_peers(&self) -> Result<SvmPeerRecords, OntapApiError> {
+        let url = format!("{}/svm/peers", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "uuid,name,svm,peer,state,applications")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        Ok(res.json().await?)
+    }
+
+    pub async fn create_svm_peer(
+        &self,
+        svm_name: &str,
+        peer_svm_name: &str,
+        peer_cluster_name: &str,
+        applications: Vec<SvmPeerApplication>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/svm/peers", self.url);
+        let body = SvmPeerCreateBody {
+            svm: NameOnly {
+                name: svm_name.into(),
+            },
+            peer: SvmPeerCreatePeer {
+                svm: NameOnly {
+                    name: peer_svm_name.into(),
+                },
+                cluster: NameOnly {
+                    name: peer_cluster_name.into(),
+                },
+            },
+            applications,
+        };
+        let res = self
+            .execute(
+                self.client
+                    .post(url)
+                    .json(&body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn accept_svm_peer(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/svm/peers/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&SvmPeerModifyBody {
+                        state: Some(SvmPeerState::Peered),
+                        ..Default::default()
+                    })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn modify_svm_peer(
+        &self,
+        uuid: &str,
+        body: &SvmPeerModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/svm/peers/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_svm_peer(&self, uuid: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/svm/peers/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_nodes(&self) -> Result<NodeRecords, OntapApiError> {
+        let url = format!("{}/cluster/nodes", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", NODE_FIELDS)]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
         }
-        let cluster: Cluster = res.json().await?;
-        let api_version = ApiVersion::try_from(cluster.version)?;
+        Ok(res.json().await?)
+    }
 
-        Ok(OntapClient {
-            url: self.url,
-            username: self.username,
-            password: self.password,
-            client: reqwest_client,
-            api_version,
-        })
+    pub async fn get_node_ha(&self, uuid: &str) -> Result<NodeHa, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "ha")]),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+        }
+        let node: NodeHaOnly = res.json().await?;
+        Ok(node.ha)
     }
-}
 
-pub struct OntapClient {
-    url: Url,
-    username: String,
-    password: String,
-    client: reqwest::Client,
-    api_version: ApiVersion,
-}
+    #[instrument(skip(self))]
+    pub async fn get_node_metrics(
+        &self,
+        uuid: &str,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{}/metrics", self.url, uuid);
 
-impl OntapClient {
-    pub async fn get_volumes(&self) -> Result<VolumeRecords, OntapApiError> {
-        let url = format!("{}/storage/volumes", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", self.api_version.volume_fields())])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(NODE_METRICS_FIELDS)),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
         }
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
+    }
+
+    pub async fn takeover_node(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{uuid}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .patch(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("action", "takeover")])
+                    .header("accept", "application/json"),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
         Ok(res.json().await?)
     }
 
-    pub async fn get_ip_interfaces(&self) -> Result<IpInterfaceRecords, OntapApiError> {
-        let url = format!("{}/network/ip/interfaces", self.url);
+    pub async fn giveback_node(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{uuid}", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[(
-                "fields",
-                "enabled,ip,ipspace,location,name,scope,service_policy,services,state,svm,uuid,vip",
-            )])
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("action", "giveback")])
+                    .header("accept", "application/json"),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
         Ok(res.json().await?)
     }
 
-    pub async fn get_ethernet_ports(&self) -> Result<PortRecords, OntapApiError> {
-        let url = format!("{}/network/ethernet/ports", self.url);
+    pub async fn reboot_node(
+        &self,
+        uuid: &str,
+        reason: &str,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{uuid}", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", self.api_version.ethernet_port_fields())])
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("action", "reboot"), ("shutdown_reboot_reason", reason)])
+                    .header("accept", "application/json"),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
         Ok(res.json().await?)
     }
 
-    pub async fn ip_interface_set_admin_status(
+    pub async fn shutdown_node(
         &self,
         uuid: &str,
-        enabled: bool,
-    ) -> Result<(), OntapApiError> {
-        let url = format!("{}/network/ip/interfaces/{uuid}", self.url);
+        reason: &str,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{uuid}", self.url);
         let res = self
-            .client
-            .patch(url)
-            .json(&IpInterfaceAdminState { enabled })
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .query(&[("action", "shutdown"), ("shutdown_reboot_reason", reason)])
+                    .header("accept", "application/json"),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCodeWithError(
@@ -194,19 +3214,23 @@ impl OntapClient {
                 res.text().await?,
             ));
         }
-        Ok(())
+        Ok(res.json().await?)
     }
 
-    pub async fn ip_interface_revert(&self, uuid: &str) -> Result<(), OntapApiError> {
-        let url = format!("{}/network/ip/interfaces/{uuid}", self.url);
+    pub async fn reboot_service_processor(&self, uuid: &str) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/cluster/nodes/{uuid}", self.url);
         let res = self
-            .client
-            .patch(url)
-            .json(&IpInterfaceHomeState {
-                location: IpInterfaceHomeStateLocation { is_home: true },
-            })
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&NodeServiceProcessorActionBody {
+                        service_processor: NodeServiceProcessorActionOnly {
+                            action: NodeServiceProcessorAction::Reboot,
+                        },
+                    })
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCodeWithError(
@@ -214,21 +3238,21 @@ impl OntapClient {
                 res.text().await?,
             ));
         }
-        Ok(())
+        Ok(res.json().await?)
     }
 
-    pub async fn get_shelves(&self) -> Result<ShelfRecords, OntapApiError> {
-        let url = format!("{}/storage/shelves", self.url);
+    pub async fn get_snapmirror_relationships(
+        &self,
+    ) -> Result<SnapmirrorRelationshipRecords, OntapApiError> {
+        let url = format!("{}/snapmirror/relationships", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[(
-                "fields",
-                "bays,connection_type,disk_count,drawers,id,uid,model,name,paths,ports,state",
-            )])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "source,destination,state,transfer,healthy")]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -236,15 +3260,19 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_s3_buckets(&self) -> Result<S3BucketRecords, OntapApiError> {
-        let url = format!("{}/protocols/s3/buckets", self.url);
+    pub async fn get_ems_events(
+        &self,
+        filter: &EmsEventFilter,
+    ) -> Result<EmsEventRecords, OntapApiError> {
+        let url = format!("{}/support/ems/events", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", self.api_version.bucket_fields())])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&filter.to_query()),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -252,105 +3280,148 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_disks(&self) -> Result<DiskRecords, OntapApiError> {
-        let url = format!("{}/storage/disks", self.url);
+    /// Returns the events since the previous poll with this cursor, querying every node of the
+    /// cluster separately. The nodes are looked up on the first poll.
+    pub async fn poll_ems_events(
+        &self,
+        cursor: &mut EmsEventCursor,
+    ) -> Result<Vec<EmsEvent>, OntapApiError> {
+        if cursor.filter.node.is_none() && cursor.nodes.is_empty() {
+            cursor.nodes = self
+                .get_nodes()
+                .await?
+                .records
+                .into_iter()
+                .map(|n| n.name)
+                .collect();
+        }
+        let mut events = vec![];
+        for filter in cursor.next_filters() {
+            events.extend(self.get_ems_events(&filter).await?.records);
+        }
+        Ok(cursor.advance(events))
+    }
+
+    pub async fn get_ems_messages(
+        &self,
+        name: Option<&str>,
+    ) -> Result<EmsMessageRecords, OntapApiError> {
+        let url = format!("{}/support/ems/messages", self.url);
+        let mut query = vec![(
+            "fields",
+            "name,severity,description,corrective_action,deprecated",
+        )];
+        if let Some(name) = name {
+            query.push(("name", name));
+        }
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[(
-                "fields",
-                "aggregates,bay,class,container_type,dr_node,fips_certified,firmware_version,home_node,model,name,node,pool,protection_mode,rated_life_used_percent,rpm,self_encrypting,serial_number,state,type,uid,usable_size,vendor",
-            )])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCodeWithError(
-                res.status().as_u16(),
-                res.text().await?,
-            ));
+            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
         }
         Ok(res.json().await?)
     }
 
-    pub async fn resize_volume(&self, uuid: &str, new_size: u64) -> Result<(), OntapApiError> {
-        let url = format!("{url}/storage/volumes/{uuid}", url = self.url, uuid = uuid);
+    pub async fn get_ems_destinations(&self) -> Result<EmsDestinationRecords, OntapApiError> {
+        let url = format!("{}/support/ems/destinations", self.url);
         let res = self
-            .client
-            .patch(url)
-            .json(&VolumeResizeBody { size: new_size })
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[(
+                        "fields",
+                        "name,type,destination,filters,certificate,syslog,system_defined",
+                    )]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
         }
-        Ok(())
+        Ok(res.json().await?)
     }
 
-    pub async fn move_volume(
+    pub async fn create_ems_destination(
         &self,
-        uuid: &str,
-        destination_aggregate_uuid: &str,
-        cutover_window: u32,
+        destination: &EmsDestination,
     ) -> Result<(), OntapApiError> {
-        let url = format!("{url}/storage/volumes/{uuid}", url = self.url, uuid = uuid);
-        let body = VolumeMoveBody {
-            movement: VolumeMoveMovement {
-                cutover_window,
-                destination_aggregate: VolumeMoveAggregate {
-                    uuid: destination_aggregate_uuid.into(),
-                },
-            },
-        };
+        let url = format!("{}/support/ems/destinations", self.url);
         let res = self
-            .client
-            .patch(url)
-            .json(&body)
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .execute(
+                self.client
+                    .post(url)
+                    .json(destination)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
         Ok(())
     }
 
-    pub async fn get_volume(&self, uuid: &str) -> Result<Volume, OntapApiError> {
-        let url = format!("{}/storage/volumes/{}", self.url, uuid);
-
+    pub async fn modify_ems_destination(
+        &self,
+        name: &str,
+        body: &EmsDestinationModifyBody,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/support/ems/destinations/{name}", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", self.api_version.volume_fields())])
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
-        Ok(res.json().await?)
+        Ok(())
     }
 
-    #[instrument(skip(self))]
-    pub async fn get_volume_metrics(
-        &self,
-        uuid: &str,
-    ) -> Result<VolumeMetricRecords, OntapApiError> {
-        let url = format!("{}/storage/volumes/{}/metrics", self.url, uuid);
+    pub async fn delete_ems_destination(&self, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/support/ems/destinations/{name}", self.url);
+        let res = self
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
+        }
+        Ok(())
+    }
 
+    pub async fn get_ems_filters(&self) -> Result<EmsFilterRecords, OntapApiError> {
+        let url = format!("{}/support/ems/filters", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[
-                ("fields", "duration,iops,throughput,status,timestamp"),
-                ("duration", "PT15S"),
-            ])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "name,rules,system_defined")]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -358,69 +3429,87 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_volume_snapshots(&self, uuid: &str) -> Result<SnapshotRecords, OntapApiError> {
-        let url = format!("{}/storage/volumes/{}/snapshots", self.url, uuid);
+    pub async fn create_ems_filter(&self, filter: &EmsFilter) -> Result<(), OntapApiError> {
+        let url = format!("{}/support/ems/filters", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", "uuid,name,owners,state")])
-            .send()
+            .execute(
+                self.client
+                    .post(url)
+                    .json(filter)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCodeWithError(
                 res.status().as_u16(),
-                res.text().await.unwrap(),
+                res.text().await?,
             ));
         }
-        Ok(res.json().await?)
+        Ok(())
     }
 
-    pub async fn get_aggregates(&self) -> Result<AggregateRecords, OntapApiError> {
-        let url = format!("{}/storage/aggregates", self.url);
+    pub async fn modify_ems_filter(
+        &self,
+        name: &str,
+        rules: Vec<EmsFilterRule>,
+    ) -> Result<(), OntapApiError> {
+        let url = format!("{}/support/ems/filters/{name}", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", "space,block_storage")])
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(&EmsFilterModifyBody { rules })
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
-        Ok(res.json().await?)
+        Ok(())
     }
 
-    pub async fn get_nfs_clients(&self) -> Result<NfsClientRecords, OntapApiError> {
-        let url = format!("{}/protocols/nfs/connected-clients", self.url);
+    pub async fn delete_ems_filter(&self, name: &str) -> Result<(), OntapApiError> {
+        let url = format!("{}/support/ems/filters/{name}", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", "client_ip,idle_duration,local_request_count,node,protocol,remote_request_count,server_ip,svm,volume")])
-            .send()
+            .execute(
+                self.client
+                    .delete(url)
+                    .basic_auth(&self.username, Some(&self.password)),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
-        Ok(res.json().await?)
+        Ok(())
     }
 
-    pub async fn get_plexes(&self, aggregate_uuid: &str) -> Result<PlexRecords, OntapApiError> {
-        let url = format!("{}/storage/aggregates/{aggregate_uuid}/plexes", self.url);
+    fn counter_table_url(&self, table: &str, rest: &[&str]) -> Url {
+        let mut url = self.url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments
+                .pop_if_empty()
+                .extend(["cluster", "counter", "tables", table])
+                .extend(rest);
+        }
+        url
+    }
+
+    pub async fn get_counter_tables(&self) -> Result<CounterTableRecords, OntapApiError> {
+        let url = format!("{}/cluster/counter/tables", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[(
-                "fields",
-                "aggregate,name,online,pool,raid_groups,resync,state",
-            )])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "name,description")]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -428,23 +3517,16 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    #[instrument(skip(self))]
-    pub async fn get_aggregate_metrics(
-        &self,
-        uuid: &str,
-    ) -> Result<AggregateMetricRecords, OntapApiError> {
-        let url = format!("{}/storage/aggregates/{}/metrics", self.url, uuid);
-
+    pub async fn get_counter_table(&self, name: &str) -> Result<CounterTable, OntapApiError> {
+        let url = self.counter_table_url(name, &[]);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[
-                ("fields", "duration,iops,throughput,status,timestamp"),
-                ("duration", "PT15S"),
-            ])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "name,description,counter_schemas")]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -452,18 +3534,25 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_jobs(&self) -> Result<JobRecords, OntapApiError> {
-        let url = format!("{}/cluster/jobs", self.url);
+    /// Rows of a counter table, optionally only with the given `counters`.
+    pub async fn get_counter_rows(
+        &self,
+        table: &str,
+        counters: Option<&[&str]>,
+    ) -> Result<CounterRowRecords, OntapApiError> {
+        let url = self.counter_table_url(table, &["rows"]);
+        let mut query = vec![("fields", "id,properties,counters".to_string())];
+        if let Some(counters) = counters {
+            query.push(("counters.name", counters.join("|")));
+        }
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[(
-                "fields",
-                "state,message,description,code,start_time,end_time",
-            )])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -471,15 +3560,20 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_svms(&self) -> Result<SvmRecords, OntapApiError> {
-        let url = format!("{}/svm/svms", self.url);
+    pub async fn get_counter_row(
+        &self,
+        table: &str,
+        id: &str,
+    ) -> Result<CounterRow, OntapApiError> {
+        let url = self.counter_table_url(table, &["rows", id]);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", "aggregates,state,cifs,ldap,nfs")])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", "id,properties,counters")]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -487,18 +3581,16 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_nodes(&self) -> Result<NodeRecords, OntapApiError> {
-        let url = format!("{}/cluster/nodes", self.url);
+    pub async fn get_cluster(&self) -> Result<Cluster, OntapApiError> {
+        let url = format!("{}/cluster", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[(
-                "fields",
-                "state,model,serial_number,uptime,service_processor",
-            )])
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&[("fields", CLUSTER_FIELDS)]),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
@@ -506,44 +3598,63 @@ impl OntapClient {
         Ok(res.json().await?)
     }
 
-    pub async fn get_snapmirror_relationships(
+    pub async fn modify_cluster(
         &self,
-    ) -> Result<SnapmirrorRelationshipRecords, OntapApiError> {
-        let url = format!("{}/snapmirror/relationships", self.url);
+        body: &ClusterModifyBody,
+    ) -> Result<JobResponse, OntapApiError> {
+        let url = format!("{}/cluster", self.url);
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .query(&[("fields", "source,destination,state,transfer,healthy")])
-            .send()
+            .execute(
+                self.client
+                    .patch(url)
+                    .json(body)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json"),
+            )
             .await?;
         if !res.status().is_success() {
-            return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
+            return Err(OntapApiError::HttpStatusCodeWithError(
+                res.status().as_u16(),
+                res.text().await?,
+            ));
         }
         Ok(res.json().await?)
     }
 
-    pub async fn get_cluster(&self) -> Result<Cluster, OntapApiError> {
-        let url = format!("{}/cluster", self.url);
+    #[instrument(skip(self))]
+    pub async fn get_cluster_metrics(
+        &self,
+        query: &MetricsQuery,
+    ) -> Result<MetricsTimeSeries, OntapApiError> {
+        let url = format!("{}/cluster/metrics", self.url);
+
         let res = self
-            .client
-            .get(url)
-            .basic_auth(&self.username, Some(&self.password))
-            .header("accept", "application/json")
-            .send()
+            .execute(
+                self.client
+                    .get(url)
+                    .basic_auth(&self.username, Some(&self.password))
+                    .header("accept", "application/json")
+                    .query(&query.to_query(METRICS_FIELDS)),
+            )
             .await?;
         if !res.status().is_success() {
             return Err(OntapApiError::HttpStatusCode(res.status().as_u16()));
         }
-        Ok(res.json().await?)
+        let metrics: MetricsRecords = res.json().await?;
+        Ok(MetricsTimeSeries::new(metrics.records))
     }
 }
 
+/// Backoff before the retry following `retries` earlier ones, doubling from 200ms up to 12.8s.
+fn retry_delay(retries: u32) -> std::time::Duration {
+    std::time::Duration::from_millis(200u64.saturating_mul(1 << retries.min(6)))
+}
+
 pub fn aggregate_metrics(metrics: Vec<MetricsRecord>) -> Vec<AggregatedMetrics> {
     metrics
         .into_iter()
         .filter(|r| r.status == Status::Ok)
+        .sorted_by_key(|r| r.duration)
         .group_by(|r| r.duration)
         .into_iter()
         .map(|(key, group)| {
@@ -585,6 +3696,7 @@ pub fn aggregate_metrics_with_duration(
         .into_iter()
         .filter(|r| r.duration == duration)
         .filter(|r| r.status == Status::Ok)
+        .sorted_by_key(|r| r.duration)
         .group_by(|r| r.duration)
         .into_iter()
         .map(|(key, group)| {
@@ -620,8 +3732,14 @@ pub fn aggregate_metrics_with_duration(
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use chrono::Utc;
 
+    use crate::{
+        models::OntapApiError, telemetry::RequestMetrics, OntapClientOptions, OntapConnectionParams,
+    };
+
     use crate::models::{AggregatedMetrics, Duration, Metrics, MetricsRecord, Status};
 
     #[test]
@@ -658,6 +3776,8 @@ mod test {
                     total: 1,
                     other: 1,
                 },
+                latency: None,
+                processor_utilization: None,
                 status: Status::Ok,
                 timestamp: Utc::now(),
             },
@@ -675,6 +3795,8 @@ mod test {
                     total: 1,
                     other: 1,
                 },
+                latency: None,
+                processor_utilization: None,
                 status: Status::Error,
                 timestamp: Utc::now(),
             },
@@ -692,6 +3814,8 @@ mod test {
                     total: 1,
                     other: 1,
                 },
+                latency: None,
+                processor_utilization: None,
                 status: Status::Ok,
                 timestamp: Utc::now(),
             },
@@ -706,7 +3830,7 @@ mod test {
                 total: 1,
                 other: 1,
             },
-            actual.get(0).unwrap().avg_iops()
+            actual.first().unwrap().avg_iops()
         );
         assert_eq!(
             Metrics {
@@ -715,11 +3839,109 @@ mod test {
                 total: 1,
                 other: 1,
             },
-            actual.get(0).unwrap().avg_throughput()
+            actual.first().unwrap().avg_throughput()
         );
         assert_eq!(
             Some(expected.clone()),
             super::aggregate_metrics_with_duration(data.clone(), Duration::P1D)
         );
     }
+
+    #[test]
+    fn aggregate_metrics_unordered() {
+        let record = |duration| MetricsRecord {
+            duration,
+            iops: Metrics::default(),
+            throughput: Metrics::default(),
+            latency: None,
+            processor_utilization: None,
+            status: Status::Ok,
+            timestamp: Utc::now(),
+        };
+        let data = vec![
+            record(Duration::PT15S),
+            record(Duration::P1D),
+            record(Duration::PT15S),
+        ];
+
+        let actual = super::aggregate_metrics(data);
+        assert_eq!(2, actual.len());
+        assert_eq!(2, actual[0].count);
+    }
+
+    #[test]
+    fn retry_delay_capped() {
+        use std::time::Duration;
+
+        assert_eq!(Duration::from_millis(200), super::retry_delay(0));
+        assert_eq!(Duration::from_millis(400), super::retry_delay(1));
+        assert_eq!(Duration::from_millis(12_800), super::retry_delay(6));
+        assert_eq!(Duration::from_millis(12_800), super::retry_delay(64));
+        assert_eq!(Duration::from_millis(12_800), super::retry_delay(u32::MAX));
+    }
+
+    /// Answers `/api/cluster`, then a 503 followed by an ONTAP error for everything else.
+    async fn stand_in(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut unavailable_sent = false;
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let (status, body) = if request.starts_with("GET /api/cluster ") {
+                (
+                    "200 OK",
+                    r#"{"version":{"full":"9.10.1","generation":9,"major":10,"minor":1}}"#,
+                )
+            } else if !unavailable_sent {
+                unavailable_sent = true;
+                ("503 Service Unavailable", "")
+            } else {
+                (
+                    "404 Not Found",
+                    r#"{"error":{"message":"entry doesn't exist","code":"4"}}"#,
+                )
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn observe_retries_and_error_codes() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(stand_in(listener));
+
+        let metrics = Arc::new(RequestMetrics::new());
+        let client = OntapConnectionParams {
+            url: format!("http://{addr}/api").parse().unwrap(),
+            username: "admin".into(),
+            password: "secret".into(),
+        }
+        .connect_with_options(OntapClientOptions {
+            max_retries: 2,
+            observer: Some(metrics.clone()),
+        })
+        .await
+        .unwrap();
+
+        let err = client.get_job("1").await.unwrap_err();
+        assert!(matches!(err, OntapApiError::HttpStatusCode(404)));
+
+        let stats = metrics.snapshot();
+        let connect = &stats[&("GET".to_string(), "/api/cluster".to_string())];
+        assert_eq!(1, connect.count);
+        assert_eq!(0, connect.errors);
+        let stats = &stats[&("GET".to_string(), "/api/cluster/jobs/{id}".to_string())];
+        assert_eq!(1, stats.count);
+        assert_eq!(1, stats.errors);
+        assert_eq!(1, stats.retries);
+        assert_eq!(Some(&1), stats.ontap_error_codes.get("4"));
+    }
 }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{MetricsRecord, NameOnly, Statistics};
+use super::{GenericThing, MetricsRecord, NameOnly, Statistics};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AggregateRecords {
@@ -12,6 +12,7 @@ pub struct AggregateRecords {
 pub struct Aggregate {
     pub uuid: String,
     pub name: String,
+    pub node: Option<GenericThing>,
     pub space: AggregateSpace,
     pub block_storage: AggregateBlockStorage,
     pub statistics: Option<Statistics>,
@@ -41,18 +42,74 @@ pub struct Efficiency {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AggregateBlockStorage {
     pub primary: AggregatePrimaryBlockStorage,
+    pub mirror: Option<AggregateMirror>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AggregatePrimaryBlockStorage {
-    pub checksum_style: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_style: Option<String>,
     pub disk_class: String,
     pub disk_count: u32,
-    pub disk_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_type: Option<String>,
     pub raid_size: u32,
     pub raid_type: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AggregateMirror {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AggregateRecommendationRecords {
+    pub records: Vec<AggregateRecommendation>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AggregateRecommendation {
+    pub name: String,
+    pub node: GenericThing,
+    pub block_storage: AggregateBlockStorage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AggregateCreateBody {
+    pub name: String,
+    pub node: NameOnly,
+    pub block_storage: AggregateCreateBlockStorage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AggregateCreateBlockStorage {
+    pub primary: AggregatePrimaryBlockStorage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<AggregateMirror>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AggregateExpandBody {
+    pub block_storage: AggregateExpandBlockStorage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AggregateExpandBlockStorage {
+    pub primary: AggregateExpandPrimary,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AggregateExpandPrimary {
+    /// Total number of disks after the expansion.
+    pub disk_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AggregateRenameBody {
+    pub name: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AggregateMetricRecords {
     pub records: Vec<MetricsRecord>,
@@ -146,3 +203,23 @@ pub enum PlexState {
     Failed,
     OutOfDate,
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{AggregateExpandBlockStorage, AggregateExpandBody, AggregateExpandPrimary};
+
+    #[test]
+    fn expand_body() {
+        let body = AggregateExpandBody {
+            block_storage: AggregateExpandBlockStorage {
+                primary: AggregateExpandPrimary { disk_count: 12 },
+            },
+        };
+        assert_eq!(
+            json!({ "block_storage": { "primary": { "disk_count": 12 } } }),
+            serde_json::to_value(&body).unwrap()
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::NameOnly;
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastDomainRecords {
+    pub records: Vec<BroadcastDomain>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastDomain {
+    pub uuid: String,
+    pub name: String,
+    pub ipspace: NameOnly,
+    pub mtu: u32,
+    pub ports: Option<Vec<BroadcastDomainPort>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastDomainPort {
+    pub uuid: String,
+    pub name: String,
+    pub node: NameOnly,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BroadcastDomainCreateBody {
+    pub name: String,
+    pub ipspace: NameOnly,
+    pub mtu: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BroadcastDomainModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{NameOnly, Version};
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerRecords {
+    pub records: Vec<ClusterPeer>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeer {
+    pub uuid: String,
+    pub name: String,
+    pub authentication: Option<ClusterPeerAuthentication>,
+    pub encryption: Option<ClusterPeerEncryption>,
+    pub ipspace: Option<NameOnly>,
+    pub remote: ClusterPeerRemote,
+    pub status: Option<ClusterPeerStatus>,
+    pub version: Option<Version>,
+}
+
+impl ClusterPeer {
+    pub fn is_available(&self) -> bool {
+        matches!(
+            self.status.as_ref().map(|s| &s.state),
+            Some(ClusterPeerAvailability::Available)
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerAuthentication {
+    pub state: Option<ClusterPeerAuthenticationState>,
+    pub expiry_time: Option<String>,
+    pub in_use: Option<ClusterPeerAuthenticationInUse>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterPeerAuthenticationState {
+    Ok,
+    Absent,
+    Pending,
+    Problem,
+    OkAndOffer,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterPeerAuthenticationInUse {
+    Ok,
+    Absent,
+    Revoked,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerEncryption {
+    pub state: Option<ClusterPeerEncryptionState>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterPeerEncryptionState {
+    None,
+    TlsPsk,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerRemote {
+    pub name: Option<String>,
+    pub serial_number: Option<String>,
+    pub ip_addresses: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerStatus {
+    pub state: ClusterPeerAvailability,
+    pub update_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterPeerAvailability {
+    Available,
+    Partial,
+    Unavailable,
+    Pending,
+    Unidentified,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterPeerCreateBody {
+    pub remote: ClusterPeerCreateRemote,
+    pub authentication: ClusterPeerCreateAuthentication,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipspace: Option<NameOnly>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterPeerCreateRemote {
+    pub ip_addresses: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ClusterPeerCreateAuthentication {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generate_passphrase: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerSetupRecords {
+    /// Only returned when a passphrase was generated; accepting a peer with a supplied
+    /// passphrase responds with an empty body.
+    #[serde(default)]
+    pub records: Vec<ClusterPeerSetup>,
+    #[serde(default)]
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerSetup {
+    pub name: Option<String>,
+    pub ip_address: Option<String>,
+    pub authentication: Option<ClusterPeerSetupAuthentication>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterPeerSetupAuthentication {
+    pub passphrase: Option<String>,
+    pub expiry_time: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterPeerModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<ClusterPeerCreateRemote>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<ClusterPeerCreateAuthentication>,
+}
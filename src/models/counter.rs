@@ -0,0 +1,256 @@
+use serde::Deserialize;
+
+use super::NameOnly;
+
+#[derive(Debug, Deserialize)]
+pub struct CounterTableRecords {
+    pub records: Vec<CounterTable>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CounterTable {
+    pub name: String,
+    pub description: Option<String>,
+    pub counter_schemas: Option<Vec<CounterSchema>>,
+}
+
+impl CounterTable {
+    pub fn schema(&self, name: &str) -> Option<&CounterSchema> {
+        self.counter_schemas
+            .iter()
+            .flatten()
+            .find(|s| s.name == name)
+    }
+
+    /// Computes the value of every counter of this table between two polls of the same row,
+    /// `elapsed` being the seconds between the polls. Counters whose type needs a previous
+    /// value, that went backwards or whose base counter did not change are left out.
+    pub fn derive(
+        &self,
+        previous: &CounterRow,
+        current: &CounterRow,
+        elapsed: f64,
+    ) -> Vec<DerivedCounter> {
+        self.counter_schemas
+            .iter()
+            .flatten()
+            .filter_map(|schema| {
+                let values = schema.derive(previous, current, elapsed)?;
+                let counter = current.counter(&schema.name)?;
+                Some(DerivedCounter {
+                    name: schema.name.clone(),
+                    unit: schema.unit.clone(),
+                    labels: counter.labels.clone(),
+                    values,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CounterSchema {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub counter_type: CounterType,
+    pub unit: Option<String>,
+    /// Base counter of `average` and `percent` counters.
+    pub denominator: Option<NameOnly>,
+}
+
+impl CounterSchema {
+    fn derive(
+        &self,
+        previous: &CounterRow,
+        current: &CounterRow,
+        elapsed: f64,
+    ) -> Option<Vec<f64>> {
+        let now = current.counter(&self.name)?.to_values();
+        if self.counter_type == CounterType::Raw {
+            return Some(now.iter().map(|v| *v as f64).collect());
+        }
+        let changes = deltas(&previous.counter(&self.name)?.to_values(), &now)?;
+        match self.counter_type {
+            CounterType::Delta => Some(changes),
+            CounterType::Rate if elapsed > 0.0 => {
+                Some(changes.iter().map(|d| d / elapsed).collect())
+            }
+            CounterType::Average | CounterType::Percent => {
+                let base = &self.denominator.as_ref()?.name;
+                let bases = deltas(
+                    &previous.counter(base)?.to_values(),
+                    &current.counter(base)?.to_values(),
+                )?;
+                let scale = if self.counter_type == CounterType::Percent {
+                    100.0
+                } else {
+                    1.0
+                };
+                // scalar base counters apply to every element of array counters
+                changes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| {
+                        let base = bases.get(i).or(bases.first())?;
+                        (*base > 0.0).then(|| d / base * scale)
+                    })
+                    .collect()
+            }
+            _ => None,
+        }
+    }
+}
+
+fn deltas(previous: &[i64], current: &[i64]) -> Option<Vec<f64>> {
+    if previous.len() != current.len() {
+        return None;
+    }
+    previous
+        .iter()
+        .zip(current)
+        .map(|(p, c)| (c >= p).then(|| (c - p) as f64))
+        .collect()
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CounterType {
+    Raw,
+    Rate,
+    Delta,
+    Average,
+    Percent,
+    String,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CounterRowRecords {
+    pub records: Vec<CounterRow>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CounterRow {
+    pub id: String,
+    pub counter_table: Option<NameOnly>,
+    pub properties: Option<Vec<CounterProperty>>,
+    pub counters: Option<Vec<Counter>>,
+}
+
+impl CounterRow {
+    pub fn counter(&self, name: &str) -> Option<&Counter> {
+        self.counters.iter().flatten().find(|c| c.name == name)
+    }
+
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .flatten()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CounterProperty {
+    pub name: String,
+    pub value: String,
+}
+
+/// Either a scalar `value` or an array of `values` with matching `labels`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Counter {
+    pub name: String,
+    pub value: Option<i64>,
+    pub values: Option<Vec<i64>>,
+    pub labels: Option<Vec<String>>,
+}
+
+impl Counter {
+    fn to_values(&self) -> Vec<i64> {
+        match (&self.values, self.value) {
+            (Some(values), _) => values.clone(),
+            (None, Some(value)) => vec![value],
+            (None, None) => vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedCounter {
+    pub name: String,
+    pub unit: Option<String>,
+    /// Set for array counters, one per value.
+    pub labels: Option<Vec<String>>,
+    pub values: Vec<f64>,
+}
+
+impl DerivedCounter {
+    /// The value of a scalar counter.
+    pub fn value(&self) -> Option<f64> {
+        self.values.first().copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{CounterRow, CounterTable};
+
+    fn row(ops: i64, latency: i64, busy: i64, elapsed: i64, sizes: [i64; 2]) -> CounterRow {
+        serde_json::from_value(json!({
+            "id": "node-01:vol1",
+            "properties": [{ "name": "node.name", "value": "node-01" }],
+            "counters": [
+                { "name": "total_ops", "value": ops },
+                { "name": "avg_latency", "value": latency },
+                { "name": "busy", "value": busy },
+                { "name": "elapsed", "value": elapsed },
+                { "name": "instance_name", "value": 0 },
+                { "name": "size_histogram", "values": sizes, "labels": ["<4k", ">=4k"] }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn derive_counters() {
+        let table: CounterTable = serde_json::from_value(json!({
+            "name": "volume",
+            "counter_schemas": [
+                { "name": "total_ops", "type": "rate", "unit": "per_sec" },
+                { "name": "avg_latency", "type": "average", "unit": "microsec",
+                  "denominator": { "name": "total_ops" } },
+                { "name": "busy", "type": "percent", "unit": "percent",
+                  "denominator": { "name": "elapsed" } },
+                { "name": "elapsed", "type": "raw", "unit": "microsec" },
+                { "name": "instance_name", "type": "string", "unit": "none" },
+                { "name": "size_histogram", "type": "delta", "unit": "none" }
+            ]
+        }))
+        .unwrap();
+
+        let previous = row(1000, 50_000, 100, 1_000_000, [10, 20]);
+        let current = row(3000, 250_000, 600, 2_000_000, [15, 40]);
+        assert_eq!(Some("node-01"), current.property("node.name"));
+
+        let derived = table.derive(&previous, &current, 10.0);
+        let value = |name: &str| derived.iter().find(|d| d.name == name).unwrap().value();
+        assert_eq!(Some(200.0), value("total_ops"));
+        assert_eq!(Some(100.0), value("avg_latency"));
+        assert_eq!(Some(0.05), value("busy"));
+        assert_eq!(Some(2_000_000.0), value("elapsed"));
+        assert!(derived.iter().all(|d| d.name != "instance_name"));
+        let histogram = derived.iter().find(|d| d.name == "size_histogram").unwrap();
+        assert_eq!(vec![5.0, 20.0], histogram.values);
+
+        // counter reset
+        let reset = row(10, 0, 0, 0, [0, 0]);
+        assert!(table.derive(&current, &reset, 10.0).len() == 1);
+    }
+}
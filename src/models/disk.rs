@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-use super::GenericThing;
+use super::{GenericThing, NameOnly};
 
 #[derive(Debug, Deserialize)]
 pub struct DiskRecords {
@@ -17,10 +19,12 @@ pub struct Disk {
     pub dr_node: Option<GenericThing>,
     pub fips_certified: Option<bool>,
     pub firmware_version: String,
-    pub home_node: GenericThing,
+    /// Not set for unassigned disks.
+    pub home_node: Option<GenericThing>,
     pub model: String,
     pub name: String,
-    pub node: GenericThing,
+    /// Not set for unassigned disks.
+    pub node: Option<GenericThing>,
     pub pool: String,
     pub protection_mode: Option<String>,
     pub rated_life_used_percent: Option<u32>,
@@ -36,6 +40,13 @@ pub struct Disk {
     pub vendor: String,
 }
 
+impl Disk {
+    /// Name of the owning node, `None` for unassigned disks.
+    pub fn node_name(&self) -> Option<&str> {
+        self.node.as_ref().map(|n| n.name.as_str())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
 pub enum DiskType {
@@ -69,7 +80,7 @@ pub enum DiskState {
     Zeroing,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
 pub enum DiskClass {
     Unknown,
@@ -81,7 +92,7 @@ pub enum DiskClass {
     Virtual,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
 pub enum ContainerType {
     Aggregate,
@@ -97,3 +108,186 @@ pub enum ContainerType {
     Remote,
     Mediator,
 }
+
+#[derive(Debug, Serialize)]
+pub struct DiskOwnershipBody {
+    pub node: NameOnly,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+}
+
+impl DiskOwnershipBody {
+    pub fn assign(node_name: &str, pool: Option<&str>) -> Self {
+        Self {
+            node: NameOnly {
+                name: node_name.into(),
+            },
+            pool: pool.map(|p| p.into()),
+        }
+    }
+
+    /// ONTAP removes the ownership when the node name is empty.
+    pub fn unassign() -> Self {
+        Self::assign("", None)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiskStateBody {
+    pub state: DiskState,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiskEncryptionOperationBody {
+    pub encryption_operation: DiskEncryptionOperation,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskEncryptionOperation {
+    SanitizeDisk,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpareAvailability {
+    pub node: String,
+    pub class: DiskClass,
+    pub count: u32,
+    pub usable_size: u64,
+    pub largest_usable_size: u64,
+}
+
+/// Summarizes the spare disks per owning node and disk class. Unassigned disks are not spares
+/// of any node and are left out.
+pub fn spare_report(disks: &[Disk]) -> Vec<SpareAvailability> {
+    let mut report: BTreeMap<(String, DiskClass), SpareAvailability> = BTreeMap::new();
+    for (node, disk) in disks
+        .iter()
+        .filter(|d| d.container_type == ContainerType::Spare)
+        .filter_map(|d| Some((d.node_name()?, d)))
+    {
+        let entry = report
+            .entry((node.to_string(), disk.class.clone()))
+            .or_insert_with(|| SpareAvailability {
+                node: node.to_string(),
+                class: disk.class.clone(),
+                count: 0,
+                usable_size: 0,
+                largest_usable_size: 0,
+            });
+        entry.count += 1;
+        entry.usable_size += disk.usable_size;
+        entry.largest_usable_size = entry.largest_usable_size.max(disk.usable_size);
+    }
+    report.into_values().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{spare_report, Disk, DiskClass, DiskOwnershipBody, SpareAvailability};
+
+    fn disk(name: &str, node: &str, class: &str, container_type: &str, size: u64) -> Disk {
+        serde_json::from_value(json!({
+            "bay": 0,
+            "class": class,
+            "container_type": container_type,
+            "firmware_version": "NA02",
+            "home_node": { "uuid": node, "name": node },
+            "model": "X357_S163A3T8ATE",
+            "name": name,
+            "node": { "uuid": node, "name": node },
+            "pool": "pool0",
+            "self_encrypting": false,
+            "serial_number": name,
+            "state": "present",
+            "type": "ssd",
+            "uid": name,
+            "usable_size": size,
+            "vendor": "NETAPP",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn spares_per_node_and_class() {
+        let disks = vec![
+            disk("1.0.0", "node-02", "solid_state", "spare", 100),
+            disk("1.0.1", "node-01", "solid_state", "spare", 200),
+            disk("1.0.2", "node-01", "solid_state", "aggregate", 200),
+            disk("1.0.3", "node-01", "capacity", "spare", 400),
+            disk("1.0.4", "node-01", "solid_state", "spare", 300),
+        ];
+
+        assert_eq!(
+            vec![
+                SpareAvailability {
+                    node: "node-01".into(),
+                    class: DiskClass::Capacity,
+                    count: 1,
+                    usable_size: 400,
+                    largest_usable_size: 400,
+                },
+                SpareAvailability {
+                    node: "node-01".into(),
+                    class: DiskClass::SolidState,
+                    count: 2,
+                    usable_size: 500,
+                    largest_usable_size: 300,
+                },
+                SpareAvailability {
+                    node: "node-02".into(),
+                    class: DiskClass::SolidState,
+                    count: 1,
+                    usable_size: 100,
+                    largest_usable_size: 100,
+                },
+            ],
+            spare_report(&disks)
+        );
+    }
+
+    #[test]
+    fn unassigned_disk() {
+        let unassigned: Disk = serde_json::from_value(json!({
+            "bay": 5,
+            "class": "solid_state",
+            "container_type": "unassigned",
+            "firmware_version": "NA02",
+            "model": "X357_S163A3T8ATE",
+            "name": "1.0.5",
+            "pool": "pool0",
+            "self_encrypting": false,
+            "serial_number": "S3SGNA0M",
+            "state": "present",
+            "type": "ssd",
+            "uid": "5002538B:0972E8A0",
+            "usable_size": 100,
+            "vendor": "NETAPP",
+        }))
+        .unwrap();
+        assert_eq!(None, unassigned.node_name());
+        assert!(unassigned.home_node.is_none());
+
+        let disks = vec![
+            unassigned,
+            disk("1.0.0", "node-01", "solid_state", "spare", 100),
+        ];
+        let report = spare_report(&disks);
+        assert_eq!(1, report.len());
+        assert_eq!("node-01", report[0].node);
+    }
+
+    #[test]
+    fn ownership_body() {
+        assert_eq!(
+            json!({ "node": { "name": "node-01" }, "pool": "pool1" }),
+            serde_json::to_value(DiskOwnershipBody::assign("node-01", Some("pool1"))).unwrap()
+        );
+        assert_eq!(
+            json!({ "node": { "name": "" } }),
+            serde_json::to_value(DiskOwnershipBody::unassign()).unwrap()
+        );
+    }
+}
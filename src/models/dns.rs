@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ip_interface::Scope, GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct DnsRecords {
+    pub records: Vec<Dns>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Dns {
+    pub svm: GenericThing,
+    pub domains: Vec<String>,
+    pub servers: Vec<String>,
+    pub scope: Option<Scope>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DnsCreateBody {
+    pub svm: NameOnly,
+    pub domains: Vec<String>,
+    pub servers: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DnsModifyBody {
+    pub domains: Vec<String>,
+    pub servers: Vec<String>,
+}
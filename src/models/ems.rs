@@ -0,0 +1,416 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::NameOnly;
+
+#[derive(Debug, Deserialize)]
+pub struct EmsEventRecords {
+    pub records: Vec<EmsEvent>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct EmsEvent {
+    pub index: u64,
+    pub time: DateTime<Utc>,
+    pub node: NameOnly,
+    pub message: EmsEventMessage,
+    pub log_message: Option<String>,
+    pub parameters: Option<Vec<EmsEventParameter>>,
+}
+
+impl EmsEvent {
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .flatten()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct EmsEventMessage {
+    pub name: String,
+    pub severity: EmsSeverity,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct EmsEventParameter {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EmsSeverity {
+    Emergency,
+    Alert,
+    Error,
+    Notice,
+    Informational,
+    Debug,
+}
+
+impl EmsSeverity {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EmsSeverity::Emergency => "emergency",
+            EmsSeverity::Alert => "alert",
+            EmsSeverity::Error => "error",
+            EmsSeverity::Notice => "notice",
+            EmsSeverity::Informational => "informational",
+            EmsSeverity::Debug => "debug",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmsMessageRecords {
+    pub records: Vec<EmsMessage>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmsMessage {
+    pub name: String,
+    pub severity: EmsSeverity,
+    pub description: Option<String>,
+    pub corrective_action: Option<String>,
+    pub deprecated: Option<bool>,
+}
+
+/// Server side filter for `/support/ems/events`. Empty fields are not filtered on.
+#[derive(Debug, Clone, Default)]
+pub struct EmsEventFilter {
+    pub severities: Vec<EmsSeverity>,
+    pub message_name: Option<String>,
+    pub node: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub after_index: Option<u64>,
+    pub max_records: Option<u32>,
+}
+
+impl EmsEventFilter {
+    pub fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![
+            (
+                "fields",
+                "index,time,node,message,log_message,parameters".to_string(),
+            ),
+            ("order_by", "index".to_string()),
+        ];
+        if !self.severities.is_empty() {
+            let severities = self
+                .severities
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join("|");
+            query.push(("message.severity", severities));
+        }
+        if let Some(name) = &self.message_name {
+            query.push(("message.name", name.clone()));
+        }
+        if let Some(node) = &self.node {
+            query.push(("node.name", node.clone()));
+        }
+        match (self.since, self.until) {
+            (Some(since), Some(until)) => query.push((
+                "time",
+                format!("{}..{}", since.to_rfc3339(), until.to_rfc3339()),
+            )),
+            (Some(since), None) => query.push(("time", format!(">={}", since.to_rfc3339()))),
+            (None, Some(until)) => query.push(("time", format!("<={}", until.to_rfc3339()))),
+            (None, None) => {}
+        }
+        if let Some(index) = self.after_index {
+            query.push(("index", format!(">{index}")));
+        }
+        if let Some(max_records) = self.max_records {
+            query.push(("max_records", max_records.to_string()));
+        }
+        query
+    }
+}
+
+/// Records fetched per node and poll when the filter sets no `max_records`.
+pub const EMS_CURSOR_MAX_RECORDS: u32 = 1000;
+
+/// Remembers the last seen event index per node, so repeated polls only return new events.
+#[derive(Debug, Clone, Default)]
+pub struct EmsEventCursor {
+    pub filter: EmsEventFilter,
+    /// Nodes to poll, besides those in `last_seen`. Ignored when the filter sets a node.
+    pub nodes: Vec<String>,
+    pub last_seen: BTreeMap<String, u64>,
+}
+
+impl EmsEventCursor {
+    pub fn new(filter: EmsEventFilter) -> Self {
+        Self {
+            filter,
+            nodes: vec![],
+            last_seen: BTreeMap::new(),
+        }
+    }
+
+    /// The filters for the next poll. Event indexes are counted per node, so there is one
+    /// filter per node starting after the last index seen on it. Without known nodes a single
+    /// filter across all nodes is returned.
+    pub fn next_filters(&self) -> Vec<EmsEventFilter> {
+        let max_records = self.filter.max_records.or(Some(EMS_CURSOR_MAX_RECORDS));
+        let nodes = match &self.filter.node {
+            Some(node) => vec![node],
+            None => self
+                .nodes
+                .iter()
+                .chain(self.last_seen.keys())
+                .sorted()
+                .dedup()
+                .collect(),
+        };
+        if nodes.is_empty() {
+            return vec![EmsEventFilter {
+                max_records,
+                ..self.filter.clone()
+            }];
+        }
+        nodes
+            .into_iter()
+            .map(|node| EmsEventFilter {
+                node: Some(node.clone()),
+                after_index: self
+                    .last_seen
+                    .get(node)
+                    .copied()
+                    .or(self.filter.after_index),
+                max_records,
+                ..self.filter.clone()
+            })
+            .collect()
+    }
+
+    /// Drops events that were already returned and records the new high water marks.
+    pub fn advance(&mut self, events: Vec<EmsEvent>) -> Vec<EmsEvent> {
+        let mut new_events = vec![];
+        for event in events {
+            let last = self.last_seen.get(&event.node.name).copied();
+            if last.map(|l| event.index > l).unwrap_or(true) {
+                self.last_seen.insert(event.node.name.clone(), event.index);
+                new_events.push(event);
+            }
+        }
+        new_events
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmsDestinationRecords {
+    pub records: Vec<EmsDestination>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EmsDestination {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: EmsDestinationType,
+    pub destination: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<NameOnly>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<EmsDestinationCertificate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syslog: Option<EmsDestinationSyslog>,
+    #[serde(skip_serializing)]
+    pub system_defined: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EmsDestinationType {
+    Email,
+    Syslog,
+    RestApi,
+    Snmp,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EmsDestinationCertificate {
+    pub ca: String,
+    pub serial_number: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct EmsDestinationSyslog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<EmsSyslogTransport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_format: Option<EmsSyslogMessageFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_format_override: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname_format_override: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EmsSyslogTransport {
+    UdpUnencrypted,
+    TcpUnencrypted,
+    TcpEncrypted,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EmsSyslogMessageFormat {
+    LegacyNetapp,
+    Rfc5424,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct EmsDestinationModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<NameOnly>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<EmsDestinationCertificate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syslog: Option<EmsDestinationSyslog>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmsFilterRecords {
+    pub records: Vec<EmsFilter>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EmsFilter {
+    pub name: String,
+    pub rules: Vec<EmsFilterRule>,
+    #[serde(skip_serializing)]
+    pub system_defined: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EmsFilterRule {
+    pub index: u32,
+    #[serde(rename = "type")]
+    pub typ: EmsFilterRuleType,
+    pub message_criteria: EmsFilterMessageCriteria,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EmsFilterRuleType {
+    Include,
+    Exclude,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct EmsFilterMessageCriteria {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_pattern: Option<String>,
+    /// Comma separated list of severities, e.g. `emergency,alert`, or `*`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severities: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snmp_trap_types: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmsFilterModifyBody {
+    pub rules: Vec<EmsFilterRule>,
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use super::{EmsEvent, EmsEventCursor, EmsEventFilter, EmsSeverity, EMS_CURSOR_MAX_RECORDS};
+
+    fn event(node: &str, index: u64) -> EmsEvent {
+        serde_json::from_value(json!({
+            "index": index,
+            "time": "2023-01-10T10:00:00+01:00",
+            "node": { "name": node },
+            "message": { "name": "disk.failed", "severity": "error" },
+            "parameters": [{ "name": "disk", "value": "1.0.1" }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn filter_query() {
+        let filter = EmsEventFilter {
+            severities: vec![EmsSeverity::Emergency, EmsSeverity::Alert],
+            node: Some("node-01".into()),
+            since: Some(Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()),
+            after_index: Some(42),
+            ..Default::default()
+        };
+        let query = filter.to_query();
+        assert!(query.contains(&("message.severity", "emergency|alert".to_string())));
+        assert!(query.contains(&("node.name", "node-01".to_string())));
+        assert!(query.contains(&("time", ">=2023-01-01T00:00:00+00:00".to_string())));
+        assert!(query.contains(&("index", ">42".to_string())));
+    }
+
+    #[test]
+    fn cursor_per_node() {
+        let mut cursor = EmsEventCursor::default();
+        let filters = cursor.next_filters();
+        assert_eq!(1, filters.len());
+        assert_eq!(None, filters[0].after_index);
+        assert_eq!(Some(EMS_CURSOR_MAX_RECORDS), filters[0].max_records);
+
+        let new = cursor.advance(vec![event("node-01", 10), event("node-02", 3)]);
+        assert_eq!(2, new.len());
+        assert_eq!(Some("1.0.1"), new[0].parameter("disk"));
+
+        let new = cursor.advance(vec![event("node-02", 4), event("node-01", 5)]);
+        assert_eq!(vec![event("node-02", 4)], new);
+    }
+
+    #[test]
+    fn cursor_disjoint_node_indexes() {
+        let mut cursor = EmsEventCursor::new(EmsEventFilter {
+            max_records: Some(50),
+            ..Default::default()
+        });
+        cursor.nodes = vec!["node-01".into(), "node-02".into(), "node-03".into()];
+        cursor.advance(vec![event("node-01", 90_000), event("node-02", 12)]);
+
+        let filters = cursor.next_filters();
+        let bounds = filters
+            .iter()
+            .map(|f| (f.node.as_deref().unwrap(), f.after_index, f.max_records))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("node-01", Some(90_000), Some(50)),
+                ("node-02", Some(12), Some(50)),
+                ("node-03", None, Some(50)),
+            ],
+            bounds
+        );
+
+        let new = cursor.advance(vec![
+            event("node-02", 13),
+            event("node-01", 90_001),
+            event("node-03", 1),
+        ]);
+        assert_eq!(3, new.len());
+        assert_eq!(Some(&1), cursor.last_seen.get("node-03"));
+    }
+}
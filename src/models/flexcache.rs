@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{volume::Volume, GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct FlexcacheRecords {
+    pub records: Vec<Flexcache>,
+    pub num_records: u32,
+}
+
+impl FlexcacheRecords {
+    pub fn caches_of(&self, origin: &Volume) -> Vec<&Flexcache> {
+        self.records
+            .iter()
+            .filter(|c| {
+                c.origins
+                    .iter()
+                    .any(|o| o.volume.name == origin.name && o.svm.name == origin.svm.name)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Flexcache {
+    pub uuid: String,
+    pub name: String,
+    pub svm: GenericThing,
+    pub origins: Vec<FlexcacheOriginEndpoint>,
+    pub aggregates: Option<Vec<GenericThing>>,
+    pub size: Option<u128>,
+    pub path: Option<String>,
+    pub dr_cache: Option<bool>,
+    pub global_file_locking_enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlexcacheOriginEndpoint {
+    pub volume: GenericThing,
+    pub svm: GenericThing,
+    pub cluster: Option<GenericThing>,
+    pub ip_address: Option<String>,
+    pub size: Option<u128>,
+    pub state: Option<FlexcacheState>,
+    pub create_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum FlexcacheState {
+    Error,
+    Online,
+    Offline,
+    Mixed,
+    Restricted,
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlexcacheOriginRecords {
+    pub records: Vec<FlexcacheOrigin>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlexcacheOrigin {
+    pub uuid: String,
+    pub name: String,
+    pub svm: GenericThing,
+    pub flexcaches: Option<Vec<FlexcacheOriginEndpoint>>,
+    pub global_file_locking_enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlexcacheCreateBody {
+    pub name: String,
+    pub svm: NameOnly,
+    pub origins: Vec<FlexcacheCreateOrigin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<Vec<NameOnly>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prepopulate: Option<FlexcachePrepopulate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlexcacheCreateOrigin {
+    pub volume: NameOnly,
+    pub svm: NameOnly,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlexcachePrepopulate {
+    pub dir_paths: Vec<String>,
+}
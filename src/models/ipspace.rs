@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct IpspaceRecords {
+    pub records: Vec<Ipspace>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Ipspace {
+    pub uuid: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpspaceBody {
+    pub name: String,
+}
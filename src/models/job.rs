@@ -27,3 +27,13 @@ pub enum JobState {
     Success,
     Failure,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct JobResponse {
+    pub job: Option<JobLink>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobLink {
+    pub uuid: String,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct LdapRecords {
+    pub records: Vec<Ldap>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ldap {
+    pub svm: GenericThing,
+    pub servers: Option<Vec<String>>,
+    pub ad_domain: Option<String>,
+    pub preferred_ad_servers: Option<Vec<String>>,
+    pub base_dn: Option<String>,
+    pub base_scope: Option<LdapScope>,
+    pub bind_dn: Option<String>,
+    pub schema: Option<String>,
+    pub port: Option<u16>,
+    pub use_start_tls: Option<bool>,
+    pub session_security: Option<LdapSessionSecurity>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum LdapScope {
+    Base,
+    Onelevel,
+    Subtree,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum LdapSessionSecurity {
+    None,
+    Sign,
+    Seal,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct LdapBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub svm: Option<NameOnly>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ad_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_scope: Option<LdapScope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bind_dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bind_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_start_tls: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_security: Option<LdapSessionSecurity>,
+}
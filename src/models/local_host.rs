@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct LocalHostRecords {
+    pub records: Vec<LocalHost>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocalHost {
+    pub owner: GenericThing,
+    pub address: String,
+    pub hostname: Option<String>,
+    pub aliases: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocalHostCreateBody {
+    pub owner: NameOnly,
+    pub address: String,
+    pub hostname: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<String>>,
+}
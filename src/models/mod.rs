@@ -3,16 +3,33 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 pub mod aggregate;
+pub mod broadcast_domain;
+pub mod cluster_peer;
+pub mod counter;
 pub mod disk;
+pub mod dns;
+pub mod ems;
+pub mod flexcache;
 pub mod ip_interface;
+pub mod ipspace;
 pub mod job;
+pub mod ldap;
+pub mod local_host;
+pub mod name_mapping;
 pub mod nfs;
+pub mod nis;
 pub mod node;
+pub mod ntp;
 pub mod port;
+pub mod qos;
+pub mod route;
+pub mod sensor;
 pub mod shelf;
 pub mod snapmirror;
 pub mod snapshot;
 pub mod svm;
+pub mod svm_peer;
+pub mod unix_user;
 pub mod volume;
 pub mod s3;
 
@@ -26,6 +43,8 @@ pub enum OntapApiError {
     HttpStatusCodeWithError(u16, String),
     #[error("Unsupported API version")]
     UnsupportedApiVersion,
+    #[error("Unexpected response: {0}")]
+    UnexpectedResponse(String),
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -34,11 +53,34 @@ pub struct GenericThing {
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Deserialize, Clone, Serialize, PartialEq, Eq)]
 pub struct NameOnly {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct UuidOnly {
+    pub uuid: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatisticsRecords {
+    pub records: Vec<StatisticsOnly>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatisticsOnly {
+    pub uuid: String,
+    pub name: String,
+    pub statistics: Option<Statistics>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CliOutput {
+    pub cli_output: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct NetworkAddress {
     pub address: String,
@@ -46,7 +88,7 @@ pub struct NetworkAddress {
     pub netmask: String,
 }
 
-#[derive(Debug, Deserialize, Copy, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Duration {
     PT15S,
@@ -57,15 +99,224 @@ pub enum Duration {
     PT5M,
 }
 
+impl Duration {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Duration::PT15S => "PT15S",
+            Duration::PT4M => "PT4M",
+            Duration::PT30M => "PT30M",
+            Duration::PT2H => "PT2H",
+            Duration::P1D => "P1D",
+            Duration::PT5M => "PT5M",
+        }
+    }
+}
+
+/// Time range ONTAP returns metrics for, the sample duration gets coarser the longer the interval.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MetricsInterval {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl MetricsInterval {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MetricsInterval::Hour => "1h",
+            MetricsInterval::Day => "1d",
+            MetricsInterval::Week => "1w",
+            MetricsInterval::Month => "1m",
+            MetricsInterval::Year => "1y",
+        }
+    }
+
+    /// Duration of the samples ONTAP returns for this interval.
+    pub fn duration(&self) -> Duration {
+        match self {
+            MetricsInterval::Hour => Duration::PT15S,
+            MetricsInterval::Day => Duration::PT5M,
+            MetricsInterval::Week => Duration::PT30M,
+            MetricsInterval::Month => Duration::PT2H,
+            MetricsInterval::Year => Duration::P1D,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsQuery {
+    /// Ignored when an `interval` is set, the duration of its samples is used instead.
+    pub duration: Duration,
+    pub interval: Option<MetricsInterval>,
+    pub from: Option<chrono::DateTime<Utc>>,
+    pub to: Option<chrono::DateTime<Utc>>,
+}
+
+impl MetricsQuery {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            interval: None,
+            from: None,
+            to: None,
+        }
+    }
+
+    pub fn to_query(&self, fields: &str) -> Vec<(&'static str, String)> {
+        let duration = self.interval.map_or(self.duration, |i| i.duration());
+        let mut query = vec![
+            ("fields", fields.to_string()),
+            ("duration", duration.as_str().to_string()),
+            ("order_by", "timestamp".to_string()),
+        ];
+        if let Some(interval) = self.interval {
+            query.push(("interval", interval.as_str().to_string()));
+        }
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => query.push((
+                "timestamp",
+                format!("{}..{}", from.to_rfc3339(), to.to_rfc3339()),
+            )),
+            (Some(from), None) => query.push(("timestamp", format!(">={}", from.to_rfc3339()))),
+            (None, Some(to)) => query.push(("timestamp", format!("<={}", to.to_rfc3339()))),
+            (None, None) => {}
+        }
+        query
+    }
+}
+
+impl Default for MetricsQuery {
+    fn default() -> Self {
+        Self::new(Duration::PT15S)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct MetricsRecord {
     pub duration: Duration,
+    #[serde(default)]
     pub iops: Metrics,
+    pub latency: Option<Metrics>,
+    #[serde(default)]
     pub throughput: Metrics,
+    /// Only reported by nodes, in percent.
+    pub processor_utilization: Option<u64>,
     pub status: Status,
     pub timestamp: chrono::DateTime<Utc>,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct MetricsRecords {
+    pub records: Vec<MetricsRecord>,
+    pub num_records: u32,
+}
+
+/// Protocols with per-SVM metrics.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SvmProtocol {
+    NfsV3,
+    NfsV4,
+    NfsV41,
+    Cifs,
+    Iscsi,
+    S3,
+}
+
+impl SvmProtocol {
+    pub fn path(&self) -> &str {
+        match self {
+            SvmProtocol::NfsV3 | SvmProtocol::NfsV4 | SvmProtocol::NfsV41 => "protocols/nfs",
+            SvmProtocol::Cifs => "protocols/cifs",
+            SvmProtocol::Iscsi => "protocols/san/iscsi",
+            SvmProtocol::S3 => "protocols/s3",
+        }
+    }
+
+    /// NFS reports each version as a nested object of the same record.
+    pub fn nfs_version(&self) -> Option<&'static str> {
+        match self {
+            SvmProtocol::NfsV3 => Some("v3"),
+            SvmProtocol::NfsV4 => Some("v4"),
+            SvmProtocol::NfsV41 => Some("v41"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NfsMetricsRecords {
+    pub records: Vec<NfsMetricsRecord>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NfsMetricsRecord {
+    pub timestamp: chrono::DateTime<Utc>,
+    pub v3: Option<NfsVersionMetrics>,
+    pub v4: Option<NfsVersionMetrics>,
+    pub v41: Option<NfsVersionMetrics>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NfsVersionMetrics {
+    pub duration: Duration,
+    #[serde(default)]
+    pub iops: Metrics,
+    pub latency: Option<Metrics>,
+    #[serde(default)]
+    pub throughput: Metrics,
+    pub status: Status,
+}
+
+impl NfsMetricsRecord {
+    pub fn version(&self, protocol: SvmProtocol) -> Option<MetricsRecord> {
+        let metrics = match protocol {
+            SvmProtocol::NfsV3 => self.v3.as_ref(),
+            SvmProtocol::NfsV4 => self.v4.as_ref(),
+            SvmProtocol::NfsV41 => self.v41.as_ref(),
+            _ => None,
+        }?;
+        Some(MetricsRecord {
+            duration: metrics.duration,
+            iops: metrics.iops.clone(),
+            latency: metrics.latency.clone(),
+            throughput: metrics.throughput.clone(),
+            processor_utilization: None,
+            status: metrics.status.clone(),
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+/// Metrics samples ordered by ascending timestamp.
+#[derive(Debug, Clone)]
+pub struct MetricsTimeSeries {
+    pub records: Vec<MetricsRecord>,
+}
+
+impl MetricsTimeSeries {
+    pub fn new(mut records: Vec<MetricsRecord>) -> Self {
+        records.sort_by_key(|r| r.timestamp);
+        Self { records }
+    }
+
+    pub fn latest(&self) -> Option<&MetricsRecord> {
+        self.records.last()
+    }
+
+    pub fn between(
+        &self,
+        from: chrono::DateTime<Utc>,
+        to: chrono::DateTime<Utc>,
+    ) -> impl Iterator<Item = &MetricsRecord> {
+        self.records
+            .iter()
+            .filter(move |r| r.timestamp >= from && r.timestamp <= to)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct AggregatedMetrics {
     pub duration: Duration,
@@ -107,11 +358,17 @@ impl std::ops::Add for AggregatedMetrics {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// Read/write/other/total triple. Objects that only report some of the values, e.g. LIF and
+/// port throughput without `other`, leave the rest at zero.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub struct Metrics {
+    #[serde(default)]
     pub read: u128,
+    #[serde(default)]
     pub write: u128,
+    #[serde(default)]
     pub total: u128,
+    #[serde(default)]
     pub other: u128,
 }
 
@@ -128,7 +385,7 @@ impl std::ops::Add for Metrics {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Status {
     Ok,
@@ -145,6 +402,8 @@ pub enum Status {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Statistics {
+    pub timestamp: Option<chrono::DateTime<Utc>>,
+    pub status: Option<Status>,
     pub iops_raw: Metrics,
     pub latency_raw: Metrics,
     pub throughput_raw: Metrics,
@@ -153,6 +412,70 @@ pub struct Statistics {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Cluster {
     pub version: Version,
+    pub name: Option<String>,
+    pub uuid: Option<String>,
+    pub location: Option<String>,
+    pub contact: Option<String>,
+    pub management_interfaces: Option<Vec<ClusterManagementInterface>>,
+    pub dns_domains: Option<Vec<String>>,
+    pub name_servers: Option<Vec<String>>,
+    pub ntp_servers: Option<Vec<String>>,
+    pub timezone: Option<NameOnly>,
+    pub certificate: Option<UuidOnly>,
+    pub peering_policy: Option<ClusterPeeringPolicy>,
+    pub metric: Option<ClusterMetric>,
+    pub statistics: Option<Statistics>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterManagementInterface {
+    pub uuid: String,
+    pub name: String,
+    pub ip: ClusterManagementInterfaceIp,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterManagementInterfaceIp {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ClusterPeeringPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption_required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_passphrase_length: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterMetric {
+    pub iops: Metrics,
+    pub throughput: Metrics,
+    pub timestamp: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ClusterModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_domains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_servers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ntp_servers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<NameOnly>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<UuidOnly>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peering_policy: Option<ClusterPeeringPolicy>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -165,13 +488,99 @@ pub struct Version {
 
 #[cfg(test)]
 mod test {
-    use super::{Duration, Metrics};
+    use chrono::{TimeZone, Utc};
+
+    use super::{
+        Duration, Metrics, MetricsInterval, MetricsQuery, MetricsRecord, MetricsTimeSeries,
+        NfsMetricsRecord, SvmProtocol,
+    };
 
     #[test]
     fn parse_duration() {
         assert_eq!(Duration::P1D, serde_json::from_str("\"P1D\"").unwrap());
     }
 
+    #[test]
+    fn metrics_query() {
+        let query = MetricsQuery {
+            interval: Some(MetricsInterval::Week),
+            from: Some(Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()),
+            ..MetricsQuery::new(Duration::PT30M)
+        };
+        let query = query.to_query("iops");
+        assert!(query.contains(&("duration", "PT30M".to_string())));
+        assert!(query.contains(&("interval", "1w".to_string())));
+        assert!(query.contains(&("timestamp", ">=2023-01-01T00:00:00+00:00".to_string())));
+    }
+
+    #[test]
+    fn metrics_query_interval_duration() {
+        let query = MetricsQuery {
+            interval: Some(MetricsInterval::Day),
+            ..MetricsQuery::default()
+        };
+        let query = query.to_query("iops");
+        assert!(query.contains(&("duration", "PT5M".to_string())));
+        assert!(query.contains(&("interval", "1d".to_string())));
+    }
+
+    #[test]
+    fn time_series_ordered() {
+        let records: Vec<MetricsRecord> = serde_json::from_value(serde_json::json!([
+            {
+                "duration": "PT15S",
+                "iops": { "read": 1, "write": 1, "other": 0, "total": 2 },
+                "latency": { "read": 100, "write": 200, "other": 0, "total": 150 },
+                "throughput": { "read": 1, "write": 1, "other": 0, "total": 2 },
+                "status": "ok",
+                "timestamp": "2023-01-01T00:00:15Z"
+            },
+            {
+                "duration": "PT15S",
+                "iops": { "read": 1, "write": 1, "other": 0, "total": 2 },
+                "throughput": { "read": 1, "write": 1, "other": 0, "total": 2 },
+                "status": "ok",
+                "timestamp": "2023-01-01T00:00:00Z"
+            }
+        ]))
+        .unwrap();
+        let series = MetricsTimeSeries::new(records);
+        assert!(series.records[0].latency.is_none());
+        assert_eq!(
+            150,
+            series.latest().unwrap().latency.as_ref().unwrap().total
+        );
+    }
+
+    #[test]
+    fn throughput_only_and_nfs_metrics() {
+        let lif: MetricsRecord = serde_json::from_value(serde_json::json!({
+            "duration": "PT15S",
+            "throughput": { "read": 10, "write": 20, "total": 30 },
+            "status": "ok",
+            "timestamp": "2023-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(0, lif.iops.total);
+        assert_eq!(30, lif.throughput.total);
+
+        let nfs: NfsMetricsRecord = serde_json::from_value(serde_json::json!({
+            "timestamp": "2023-01-01T00:00:00Z",
+            "v3": {
+                "duration": "PT15S",
+                "iops": { "read": 1, "write": 2, "other": 3, "total": 6 },
+                "latency": { "read": 100, "write": 200, "other": 50, "total": 120 },
+                "throughput": { "read": 10, "write": 20, "other": 0, "total": 30 },
+                "status": "ok"
+            }
+        }))
+        .unwrap();
+        assert!(nfs.version(SvmProtocol::NfsV4).is_none());
+        let v3 = nfs.version(SvmProtocol::NfsV3).unwrap();
+        assert_eq!(6, v3.iops.total);
+        assert_eq!(nfs.timestamp, v3.timestamp);
+    }
+
     #[test]
     fn add_metrics() {
         assert_eq!(
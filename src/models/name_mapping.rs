@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct NameMappingRecords {
+    pub records: Vec<NameMapping>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NameMapping {
+    pub svm: GenericThing,
+    pub direction: NameMappingDirection,
+    pub index: u32,
+    pub pattern: String,
+    pub replacement: String,
+    pub client_match: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum NameMappingDirection {
+    WinUnix,
+    UnixWin,
+    KrbUnix,
+    S3Unix,
+    S3Win,
+}
+
+impl NameMappingDirection {
+    pub fn as_str(&self) -> &str {
+        match self {
+            NameMappingDirection::WinUnix => "win_unix",
+            NameMappingDirection::UnixWin => "unix_win",
+            NameMappingDirection::KrbUnix => "krb_unix",
+            NameMappingDirection::S3Unix => "s3_unix",
+            NameMappingDirection::S3Win => "s3_win",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct NameMappingCreateBody {
+    pub svm: NameOnly,
+    pub direction: NameMappingDirection,
+    pub index: u32,
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_match: Option<String>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct NisRecords {
+    pub records: Vec<Nis>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Nis {
+    pub svm: GenericThing,
+    pub domain: String,
+    pub servers: Vec<String>,
+    pub bound_servers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NisCreateBody {
+    pub svm: NameOnly,
+    pub domain: String,
+    pub servers: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NisModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<String>>,
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use super::NetworkAddress;
+use super::{
+    sensor::{ChassisFruType, ChassisState},
+    GenericThing, NetworkAddress, Version,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct NodeRecords {
@@ -17,6 +20,93 @@ pub struct Node {
     pub serial_number: String,
     pub uptime: u64,
     pub service_processor: NodeServiceProcessor,
+    pub ha: Option<NodeHa>,
+    pub version: Option<Version>,
+    pub location: Option<String>,
+    pub system_id: Option<String>,
+    pub vendor_serial_number: Option<String>,
+    pub management_interfaces: Option<Vec<NodeManagementInterface>>,
+    pub controller: Option<NodeController>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeManagementInterface {
+    pub uuid: String,
+    pub name: String,
+    pub ip: Option<NodeInterfaceIp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeInterfaceIp {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeController {
+    pub board: Option<String>,
+    pub cpu: Option<NodeCpu>,
+    pub memory_size: Option<u64>,
+    pub nvram: Option<NodeNvram>,
+    pub frus: Option<Vec<NodeFru>>,
+    pub over_temperature: Option<NodeOverTemperature>,
+    pub failed_fan: Option<NodeFailedComponent>,
+    pub failed_power_supply: Option<NodeFailedComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeCpu {
+    pub count: Option<u32>,
+    pub firmware_release: Option<String>,
+    pub processor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeNvram {
+    pub id: Option<u64>,
+    pub battery_state: Option<NodeNvramBatteryState>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeNvramBatteryState {
+    BatteryOk,
+    BatteryPartiallyDischarged,
+    BatteryFullyDischarged,
+    BatteryNotPresent,
+    BatteryNearEndOfLife,
+    BatteryAtEndOfLife,
+    BatteryUnknown,
+    BatteryOverCharged,
+    BatteryFullyCharged,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeFru {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub typ: ChassisFruType,
+    pub state: ChassisState,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeOverTemperature {
+    Over,
+    Normal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeFailedComponent {
+    pub count: Option<u32>,
+    pub message: Option<NodeFailedComponentMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeFailedComponentMessage {
+    pub code: Option<String>,
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -51,3 +141,138 @@ pub enum NodeServiceProcessorState {
     NodeOffline,
     SpDaemonOffline,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHaOnly {
+    pub ha: NodeHa,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHa {
+    pub enabled: bool,
+    pub auto_giveback: Option<bool>,
+    pub partners: Option<Vec<GenericThing>>,
+    pub ports: Option<Vec<NodeHaPort>>,
+    pub interconnect: Option<NodeHaInterconnect>,
+    pub takeover: Option<NodeHaTakeover>,
+    pub giveback: Option<NodeHaGiveback>,
+}
+
+impl NodeHa {
+    pub fn partner(&self) -> Option<&GenericThing> {
+        self.partners.as_ref().and_then(|p| p.first())
+    }
+
+    pub fn is_takeover_possible(&self) -> bool {
+        let interconnect_up = matches!(
+            self.interconnect.as_ref().and_then(|i| i.state.as_ref()),
+            Some(NodeHaInterconnectState::Up)
+        );
+        let takeover_state = self.takeover.as_ref().map(|t| &t.state);
+        self.enabled
+            && interconnect_up
+            && !matches!(
+                takeover_state,
+                Some(NodeHaTakeoverState::NotPossible | NodeHaTakeoverState::InProgress)
+            )
+    }
+
+    pub fn is_failover_in_progress(&self) -> bool {
+        matches!(
+            self.takeover.as_ref().map(|t| &t.state),
+            Some(NodeHaTakeoverState::InProgress)
+        ) || matches!(
+            self.giveback.as_ref().map(|g| &g.state),
+            Some(NodeHaGivebackState::InProgress)
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHaPort {
+    pub number: u32,
+    pub state: NodeHaPortState,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeHaPortState {
+    Down,
+    Initialized,
+    Armed,
+    Reserved,
+    Active,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHaInterconnect {
+    pub adapter: Option<String>,
+    pub state: Option<NodeHaInterconnectState>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeHaInterconnectState {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHaTakeover {
+    pub state: NodeHaTakeoverState,
+    pub failure: Option<NodeHaFailure>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeHaTakeoverState {
+    NotPossible,
+    NotAttempted,
+    InTakeover,
+    InProgress,
+    Failed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHaGiveback {
+    pub state: NodeHaGivebackState,
+    pub failure: Option<NodeHaFailure>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeHaGivebackState {
+    NothingToGiveback,
+    NotAttempted,
+    InProgress,
+    Failed,
+    PartialGiveback,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeHaFailure {
+    pub code: Option<u64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeServiceProcessorActionBody {
+    pub service_processor: NodeServiceProcessorActionOnly,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeServiceProcessorActionOnly {
+    pub action: NodeServiceProcessorAction,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeServiceProcessorAction {
+    Reboot,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct NtpServerRecords {
+    pub records: Vec<NtpServer>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NtpServer {
+    pub server: String,
+    pub version: Option<NtpVersion>,
+    pub authentication_enabled: Option<bool>,
+    pub key: Option<NtpKey>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NtpVersion {
+    #[serde(rename = "3")]
+    V3,
+    #[serde(rename = "4")]
+    V4,
+    Auto,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NtpKey {
+    pub id: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NtpServerCreateBody {
+    pub server: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<NtpVersion>,
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{GenericThing, NameOnly};
+use super::{GenericThing, NameOnly, UuidOnly};
 
 #[derive(Debug, Deserialize)]
 pub struct PortRecords {
@@ -77,3 +77,45 @@ pub enum PortType {
     Physical,
     Lag,
 }
+
+#[derive(Debug, Serialize)]
+pub struct PortCreateBody {
+    #[serde(rename = "type")]
+    pub typ: PortType,
+    pub node: NameOnly,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcast_domain: Option<PortBroadcastDomainRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan: Option<PortCreateVlan>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lag: Option<PortCreateLag>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortCreateVlan {
+    pub base_port: UuidOnly,
+    pub tag: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortCreateLag {
+    pub mode: PortLagMode,
+    pub distribution_policy: PortLagDistributionPolicy,
+    pub member_ports: Vec<UuidOnly>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PortBroadcastDomainRef {
+    pub name: String,
+    pub ipspace: NameOnly,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct PortModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcast_domain: Option<PortBroadcastDomainRef>,
+}
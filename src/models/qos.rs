@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct QosPolicyRecords {
+    pub records: Vec<QosPolicy>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QosPolicy {
+    pub uuid: String,
+    pub name: String,
+    pub svm: Option<GenericThing>,
+    pub pgid: Option<u32>,
+    pub object_count: Option<u32>,
+    pub fixed: Option<QosPolicyFixed>,
+    pub adaptive: Option<QosPolicyAdaptive>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct QosPolicyFixed {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_throughput_iops: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_throughput_mbps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_throughput_iops: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_throughput_mbps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity_shared: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct QosPolicyAdaptive {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_iops: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_iops: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absolute_min_iops: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_iops_allocation: Option<QosIopsAllocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_iops_allocation: Option<QosIopsAllocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_size: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum QosIopsAllocation {
+    UsedSpace,
+    AllocatedSpace,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QosPolicyCreateBody {
+    pub name: String,
+    pub svm: NameOnly,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<QosPolicyFixed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<QosPolicyAdaptive>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct QosPolicyModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<QosPolicyFixed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<QosPolicyAdaptive>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct QosPolicyRef {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VolumeQosBody {
+    pub qos: VolumeQosPolicy,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct VolumeQosPolicy {
+    pub policy: QosPolicyRef,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LunQosBody {
+    pub qos_policy: QosPolicyRef,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QosWorkloadRecords {
+    pub records: Vec<QosWorkload>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QosWorkload {
+    pub uuid: String,
+    pub name: String,
+    pub svm: Option<GenericThing>,
+    pub policy: Option<QosPolicyRef>,
+    pub workload_class: Option<QosWorkloadClass>,
+    pub wid: Option<u64>,
+    pub volume: Option<String>,
+    pub lun: Option<String>,
+    pub file: Option<String>,
+    pub qtree: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum QosWorkloadClass {
+    Undefined,
+    Preset,
+    UserDefined,
+    SystemDefined,
+    Autovolume,
+    LoadControl,
+}
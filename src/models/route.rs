@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ip_interface::{IpInfo, Scope},
+    GenericThing, NameOnly,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct NetworkRouteRecords {
+    pub records: Vec<NetworkRoute>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NetworkRoute {
+    pub uuid: String,
+    pub destination: IpInfo,
+    pub gateway: String,
+    pub ipspace: Option<GenericThing>,
+    pub metric: Option<u32>,
+    pub scope: Scope,
+    pub svm: Option<GenericThing>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkRouteCreateBody {
+    pub destination: NetworkRouteDestination,
+    pub gateway: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub svm: Option<NameOnly>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipspace: Option<NameOnly>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkRouteDestination {
+    pub address: String,
+    pub netmask: String,
+}
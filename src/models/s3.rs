@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{GenericThing, MetricsRecord, Statistics, volume::VolumeEncryption};
+use super::{GenericThing, volume::VolumeEncryption};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct S3BucketRecords {
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use super::GenericThing;
+
+#[derive(Debug, Deserialize)]
+pub struct SensorRecords {
+    pub records: Vec<Sensor>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Sensor {
+    pub node: GenericThing,
+    pub index: u32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: SensorType,
+    pub value: Option<i64>,
+    pub value_units: Option<String>,
+    pub threshold_state: Option<SensorThresholdState>,
+    pub critical_low_threshold: Option<i64>,
+    pub warning_low_threshold: Option<i64>,
+    pub warning_high_threshold: Option<i64>,
+    pub critical_high_threshold: Option<i64>,
+    pub discrete_state: Option<String>,
+    pub discrete_value: Option<String>,
+}
+
+impl Sensor {
+    pub fn is_normal(&self) -> bool {
+        matches!(
+            self.threshold_state,
+            None | Some(SensorThresholdState::Normal)
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorType {
+    Agent,
+    BatteryLife,
+    Counter,
+    Current,
+    Discrete,
+    Fan,
+    Fru,
+    Minutes,
+    Nvmem,
+    Percent,
+    Thermal,
+    Voltage,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorThresholdState {
+    Normal,
+    LowWarning,
+    HighWarning,
+    LowCritical,
+    HighCritical,
+    Failed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChassisRecords {
+    pub records: Vec<Chassis>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Chassis {
+    pub id: String,
+    pub state: ChassisState,
+    pub nodes: Option<Vec<ChassisNode>>,
+    pub frus: Option<Vec<ChassisFru>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChassisNode {
+    pub uuid: String,
+    pub name: String,
+    pub position: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChassisFru {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub typ: ChassisFruType,
+    pub state: ChassisState,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ChassisState {
+    Ok,
+    Error,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ChassisFruType {
+    Fan,
+    Psu,
+    Pcie,
+    Disk,
+    Nvs,
+    Dimm,
+    Controller,
+    #[serde(other)]
+    Unknown,
+}
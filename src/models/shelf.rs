@@ -24,6 +24,12 @@ pub struct Shelf {
     pub paths: Vec<Path>,
     pub ports: Option<Vec<Port>>,
     pub state: ShelfState,
+    pub acps: Option<Vec<Acp>>,
+    pub current_sensors: Option<Vec<CurrentSensor>>,
+    pub fans: Option<Vec<Fan>>,
+    pub frus: Option<Vec<Fru>>,
+    pub temperature_sensors: Option<Vec<TemperatureSensor>>,
+    pub voltage_sensors: Option<Vec<VoltageSensor>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -128,3 +134,96 @@ pub struct Cable {
     pub part_number: Option<String>,
     pub serial_number: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Acp {
+    pub enabled: bool,
+    pub channel: Option<String>,
+    pub connection_state: Option<AcpConnectionState>,
+    pub node: Option<GenericThing>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcpConnectionState {
+    NoConnectivity,
+    PartialConnectivity,
+    FullConnectivity,
+    AdditionalConnectivity,
+    #[serde(other)]
+    UnknownConnectivity,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Fan {
+    pub id: u64,
+    pub location: Option<String>,
+    pub rpm: Option<u64>,
+    pub state: ComponentState,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Fru {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub typ: FruType,
+    pub firmware_version: Option<String>,
+    pub installed: Option<bool>,
+    pub part_number: Option<String>,
+    pub serial_number: Option<String>,
+    pub state: ComponentState,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FruType {
+    Module,
+    Psu,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TemperatureSensor {
+    pub id: u64,
+    pub location: Option<String>,
+    pub ambient: Option<bool>,
+    pub temperature: Option<i64>,
+    pub threshold: Option<TemperatureThreshold>,
+    pub state: ComponentState,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TemperatureThreshold {
+    pub high: Option<TemperatureLimit>,
+    pub low: Option<TemperatureLimit>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TemperatureLimit {
+    pub critical: Option<i64>,
+    pub warning: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VoltageSensor {
+    pub id: u64,
+    pub location: Option<String>,
+    pub voltage: Option<f64>,
+    pub state: ComponentState,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CurrentSensor {
+    pub id: u64,
+    pub location: Option<String>,
+    pub current: Option<u64>,
+    pub state: ComponentState,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    Ok,
+    Error,
+}
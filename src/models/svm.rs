@@ -17,6 +17,8 @@ pub struct Svm {
     pub cifs: Option<SvmCifs>,
     pub ldap: Option<SvmLdap>,
     pub nfs: Option<SvmNfs>,
+    pub nis: Option<SvmNis>,
+    pub nsswitch: Option<SvmNsswitch>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -43,3 +45,38 @@ pub struct SvmLdap {
 pub struct SvmNfs {
     pub enabled: Option<bool>,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SvmNis {
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SvmNsswitch {
+    pub group: Option<Vec<NsswitchSource>>,
+    pub hosts: Option<Vec<NsswitchSource>>,
+    pub namemap: Option<Vec<NsswitchSource>>,
+    pub netgroup: Option<Vec<NsswitchSource>>,
+    pub passwd: Option<Vec<NsswitchSource>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum NsswitchSource {
+    Files,
+    Dns,
+    Ldap,
+    Nis,
+}
+
+impl NsswitchSource {
+    pub fn from_cli_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "files" => Some(Self::Files),
+            "dns" => Some(Self::Dns),
+            "ldap" => Some(Self::Ldap),
+            "nis" => Some(Self::Nis),
+            _ => None,
+        }
+    }
+}
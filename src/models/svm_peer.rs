@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct SvmPeerRecords {
+    pub records: Vec<SvmPeer>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SvmPeer {
+    pub uuid: String,
+    pub name: Option<String>,
+    pub svm: GenericThing,
+    pub peer: SvmPeerPeer,
+    pub state: SvmPeerState,
+    pub applications: Option<Vec<SvmPeerApplication>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SvmPeerPeer {
+    pub svm: GenericThing,
+    pub cluster: GenericThing,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SvmPeerState {
+    Peered,
+    Rejected,
+    Suspended,
+    Initiated,
+    Pending,
+    Initializing,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SvmPeerApplication {
+    Snapmirror,
+    FileCopy,
+    LunCopy,
+    Flexcache,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SvmPeerCreateBody {
+    pub svm: NameOnly,
+    pub peer: SvmPeerCreatePeer,
+    pub applications: Vec<SvmPeerApplication>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SvmPeerCreatePeer {
+    pub svm: NameOnly,
+    pub cluster: NameOnly,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct SvmPeerModifyBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<SvmPeerState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applications: Option<Vec<SvmPeerApplication>>,
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use super::{svm::NsswitchSource, GenericThing, NameOnly};
+
+#[derive(Debug, Deserialize)]
+pub struct UnixUserRecords {
+    pub records: Vec<UnixUser>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnixUser {
+    pub svm: GenericThing,
+    pub name: String,
+    pub id: u64,
+    pub primary_gid: u64,
+    pub full_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnixUserCreateBody {
+    pub svm: NameOnly,
+    pub name: String,
+    pub id: u64,
+    pub primary_gid: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnixGroupRecords {
+    pub records: Vec<UnixGroup>,
+    pub num_records: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnixGroup {
+    pub svm: GenericThing,
+    pub name: String,
+    pub id: u64,
+    pub users: Option<Vec<NameOnly>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnixGroupCreateBody {
+    pub svm: NameOnly,
+    pub name: String,
+    pub id: u64,
+}
+
+/// Result of resolving a user name through the name service sources configured for a SVM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixUserLookup {
+    pub name: String,
+    pub uid: u64,
+    pub gid: u64,
+    pub source: Option<NsswitchSource>,
+}
+
+impl UnixUserLookup {
+    /// Parses the output of `vserver services name-service getxxbyyy getpwbyname -show-source true`.
+    pub fn from_cli_output(output: &str) -> Option<Self> {
+        let mut name = None;
+        let mut uid = None;
+        let mut gid = None;
+        let mut source = None;
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "pw_name" => name = Some(value.to_string()),
+                "pw_uid" => uid = value.parse().ok(),
+                "pw_gid" => gid = value.parse().ok(),
+                "Source used for lookup" => source = NsswitchSource::from_cli_name(value),
+                _ => {}
+            }
+        }
+        Some(Self {
+            name: name?,
+            uid: uid?,
+            gid: gid?,
+            source,
+        })
+    }
+
+    /// Whether an error returned by `getpwbyname` means the user does not exist, e.g.
+    /// `Failed to resolve jdoe. Reason: Entry not found for "username: jdoe".`
+    pub fn is_not_found(error: &str) -> bool {
+        error.to_ascii_lowercase().contains("entry not found")
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnixUserLookupBody {
+    pub vserver: String,
+    pub username: String,
+    pub show_source: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::UnixUserLookup;
+    use crate::models::svm::NsswitchSource;
+
+    #[test]
+    fn parse_getpwbyname_output() {
+        let output = "pw_name: jdoe\npw_passwd: \npw_uid: 1001\npw_gid: 100\npw_gecos: \npw_dir: /home/jdoe\npw_shell: /bin/sh\nSource used for lookup: LDAP\n";
+        assert_eq!(
+            Some(UnixUserLookup {
+                name: "jdoe".into(),
+                uid: 1001,
+                gid: 100,
+                source: Some(NsswitchSource::Ldap),
+            }),
+            UnixUserLookup::from_cli_output(output)
+        );
+        assert_eq!(
+            None,
+            UnixUserLookup::from_cli_output("Error: entry not found")
+        );
+        let error = r#"{"error":{"message":"Error: command failed: Failed to resolve jdoe. Reason: Entry not found for \"username: jdoe\".","code":"262179"}}"#;
+        assert!(UnixUserLookup::is_not_found(error));
+        assert!(!UnixUserLookup::is_not_found(
+            r#"{"error":{"message":"Vserver not found","code":"2"}}"#
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{GenericThing, MetricsRecord, Statistics};
+use super::{qos::VolumeQosPolicy, GenericThing, MetricsRecord, NameOnly, Statistics};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct VolumeRecords {
@@ -25,6 +25,7 @@ pub struct Volume {
     pub movement: Option<VolumeMovement>,
     pub efficiency: Option<VolumeEfficiency>,
     pub constituents: Option<Vec<VolumeConstituents>>,
+    pub qos: Option<VolumeQosPolicy>,
 }
 
 impl Volume {
@@ -95,6 +96,7 @@ pub struct VolumeClone {
     pub is_flexclone: bool,
     pub parent_volume: Option<GenericThing>,
     pub parent_svm: Option<GenericThing>,
+    pub parent_snapshot: Option<NameOnly>,
     pub split_complete_percent: Option<u128>,
     pub split_estimate: Option<u128>,
     pub split_initiated: Option<bool>,
@@ -201,3 +203,33 @@ pub struct VolumeMoveMovement {
 pub struct VolumeMoveAggregate {
     pub uuid: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct VolumeCloneOnly {
+    pub clone: VolumeClone,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VolumeCloneCreateBody {
+    pub name: String,
+    pub svm: NameOnly,
+    pub clone: VolumeCloneCreateClone,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VolumeCloneCreateClone {
+    pub is_flexclone: bool,
+    pub parent_volume: NameOnly,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot: Option<NameOnly>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VolumeCloneSplitBody {
+    pub clone: VolumeCloneSplit,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VolumeCloneSplit {
+    pub split_initiated: bool,
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{Metrics, Statistics, Status};
+
+/// Per-second read/write/other/total values derived from two raw counter samples.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rate {
+    pub read: f64,
+    pub write: f64,
+    pub other: f64,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatisticsRates {
+    /// Timestamp of the newer sample.
+    pub timestamp: DateTime<Utc>,
+    /// Seconds between the two samples.
+    pub interval: f64,
+    /// Operations per second.
+    pub iops: Rate,
+    /// Bytes per second.
+    pub throughput: Rate,
+    /// Average latency per operation in microseconds, zero when no operations happened.
+    pub latency: Rate,
+}
+
+#[derive(Debug, Clone)]
+struct Sample {
+    timestamp: DateTime<Utc>,
+    statistics: Statistics,
+}
+
+/// Turns the cumulative `*_raw` counters of `Statistics` into rates.
+///
+/// Keeps the last sample per key (usually the volume or aggregate uuid). Samples without
+/// data or with a non `ok` status are skipped and the previous sample stays the baseline, so
+/// a missing sample just yields a rate over a longer interval. A counter going backwards
+/// (node reboot, takeover, volume move) resets the baseline without producing a rate.
+#[derive(Debug, Clone, Default)]
+pub struct StatisticsSampler {
+    samples: HashMap<String, Sample>,
+    /// Baselines older than this are replaced instead of used.
+    pub max_interval: Option<chrono::Duration>,
+}
+
+impl StatisticsSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a sample. `fetched_at` is used when ONTAP did not return a timestamp.
+    pub fn record(
+        &mut self,
+        key: &str,
+        statistics: &Statistics,
+        fetched_at: DateTime<Utc>,
+    ) -> Option<StatisticsRates> {
+        if statistics.status.as_ref().is_some_and(|s| *s != Status::Ok) {
+            return None;
+        }
+        let current = Sample {
+            timestamp: statistics.timestamp.unwrap_or(fetched_at),
+            statistics: statistics.clone(),
+        };
+        let rates = match self.samples.get(key) {
+            Some(previous) if current.timestamp <= previous.timestamp => return None,
+            Some(previous)
+                if self
+                    .max_interval
+                    .is_some_and(|max| current.timestamp - previous.timestamp > max) =>
+            {
+                None
+            }
+            Some(previous) => rates(previous, &current),
+            None => None,
+        };
+        self.samples.insert(key.to_string(), current);
+        rates
+    }
+
+    /// Drops baselines of keys not in `keys`, e.g. deleted volumes.
+    pub fn retain(&mut self, keys: &[&str]) {
+        self.samples.retain(|k, _| keys.contains(&k.as_str()));
+    }
+
+    pub fn forget(&mut self, key: &str) {
+        self.samples.remove(key);
+    }
+}
+
+fn rates(previous: &Sample, current: &Sample) -> Option<StatisticsRates> {
+    let interval = (current.timestamp - previous.timestamp).num_milliseconds() as f64 / 1000.0;
+    let ops = delta(&previous.statistics.iops_raw, &current.statistics.iops_raw)?;
+    let bytes = delta(
+        &previous.statistics.throughput_raw,
+        &current.statistics.throughput_raw,
+    )?;
+    let latency = delta(
+        &previous.statistics.latency_raw,
+        &current.statistics.latency_raw,
+    )?;
+    Some(StatisticsRates {
+        timestamp: current.timestamp,
+        interval,
+        iops: per_second(&ops, interval),
+        throughput: per_second(&bytes, interval),
+        latency: Rate {
+            read: ratio(latency.read, ops.read as f64),
+            write: ratio(latency.write, ops.write as f64),
+            other: ratio(latency.other, ops.other as f64),
+            total: ratio(latency.total, ops.total as f64),
+        },
+    })
+}
+
+fn delta(previous: &Metrics, current: &Metrics) -> Option<Metrics> {
+    Some(Metrics {
+        read: current.read.checked_sub(previous.read)?,
+        write: current.write.checked_sub(previous.write)?,
+        total: current.total.checked_sub(previous.total)?,
+        other: current.other.checked_sub(previous.other)?,
+    })
+}
+
+fn per_second(metrics: &Metrics, interval: f64) -> Rate {
+    Rate {
+        read: ratio(metrics.read, interval),
+        write: ratio(metrics.write, interval),
+        other: ratio(metrics.other, interval),
+        total: ratio(metrics.total, interval),
+    }
+}
+
+fn ratio(value: u128, base: f64) -> f64 {
+    if base > 0.0 {
+        value as f64 / base
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::StatisticsSampler;
+    use crate::models::{Metrics, Statistics, Status};
+
+    fn stats(ops: u128, bytes: u128, latency: u128, status: Status) -> Statistics {
+        let m = |v: u128| Metrics {
+            read: v,
+            write: 0,
+            other: 0,
+            total: v,
+        };
+        Statistics {
+            timestamp: None,
+            status: Some(status),
+            iops_raw: m(ops),
+            latency_raw: m(latency),
+            throughput_raw: m(bytes),
+        }
+    }
+
+    #[test]
+    fn rates_resets_and_gaps() {
+        let t = |s: i64| Utc.timestamp_opt(1_700_000_000 + s, 0).unwrap();
+        let mut sampler = StatisticsSampler::new();
+
+        assert!(sampler
+            .record("v", &stats(1000, 4096, 0, Status::Ok), t(0))
+            .is_none());
+
+        let rates = sampler
+            .record("v", &stats(3000, 8192, 4000, Status::Ok), t(10))
+            .unwrap();
+        assert_eq!(10.0, rates.interval);
+        assert_eq!(200.0, rates.iops.total);
+        assert_eq!(409.6, rates.throughput.read);
+        assert_eq!(2.0, rates.latency.total);
+        assert_eq!(0.0, rates.latency.write);
+
+        // missing sample keeps the baseline
+        assert!(sampler
+            .record("v", &stats(0, 0, 0, Status::PartialNoData), t(20))
+            .is_none());
+        let rates = sampler
+            .record("v", &stats(5000, 8192, 4000, Status::Ok), t(30))
+            .unwrap();
+        assert_eq!(20.0, rates.interval);
+        assert_eq!(100.0, rates.iops.total);
+
+        // counter reset
+        assert!(sampler
+            .record("v", &stats(10, 10, 10, Status::Ok), t(40))
+            .is_none());
+        assert!(sampler
+            .record("v", &stats(110, 10, 10, Status::Ok), t(50))
+            .is_some());
+    }
+}
@@ -0,0 +1,174 @@
+use std::{collections::BTreeMap, sync::Mutex, time::Duration};
+
+use reqwest::Method;
+
+/// Latency histogram bucket bounds in seconds.
+pub const LATENCY_BUCKETS: [f64; 10] = [0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Outcome of one API call, including all retries.
+#[derive(Debug)]
+pub struct RequestEvent<'a> {
+    pub method: &'a Method,
+    pub path: &'a str,
+    /// `None` when no response was received.
+    pub status: Option<u16>,
+    pub ontap_error_code: Option<&'a str>,
+    pub retries: u32,
+    pub latency: Duration,
+}
+
+impl RequestEvent<'_> {
+    pub fn is_error(&self) -> bool {
+        !matches!(self.status, Some(status) if status < 400)
+    }
+}
+
+/// Hook called by `OntapClient` after every API call.
+pub trait RequestObserver: Send + Sync {
+    fn observe(&self, event: &RequestEvent);
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RequestStats {
+    pub count: u64,
+    pub errors: u64,
+    pub retries: u64,
+    pub latency_sum: Duration,
+    /// Cumulative counts per `LATENCY_BUCKETS` bound.
+    pub latency_buckets: [u64; LATENCY_BUCKETS.len()],
+    /// Failed requests per ONTAP error code.
+    pub ontap_error_codes: BTreeMap<String, u64>,
+}
+
+/// `RequestObserver` counting requests, errors and latencies per method and `path_template`.
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    stats: Mutex<BTreeMap<(String, String), RequestStats>>,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stats keyed by method and path template.
+    pub fn snapshot(&self) -> BTreeMap<(String, String), RequestStats> {
+        self.stats.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+impl RequestObserver for RequestMetrics {
+    fn observe(&self, event: &RequestEvent) {
+        let Ok(mut stats) = self.stats.lock() else {
+            return;
+        };
+        let entry = stats
+            .entry((event.method.to_string(), path_template(event.path)))
+            .or_default();
+        entry.count += 1;
+        entry.errors += u64::from(event.is_error());
+        entry.retries += u64::from(event.retries);
+        entry.latency_sum += event.latency;
+        if let Some(code) = event.ontap_error_code {
+            *entry.ontap_error_codes.entry(code.to_string()).or_default() += 1;
+        }
+        let seconds = event.latency.as_secs_f64();
+        for (bucket, bound) in entry.latency_buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            *bucket += u64::from(seconds <= bound);
+        }
+    }
+}
+
+/// Collections addressed by name instead of uuid, with the number of key segments that follow.
+const NAME_KEYED_COLLECTIONS: [(&[&str], usize); 8] = [
+    (&["rows"], 1),
+    (&["ntp", "servers"], 1),
+    (&["ems", "destinations"], 1),
+    (&["ems", "filters"], 1),
+    (&["name-services", "unix-users"], 2),
+    (&["name-services", "unix-groups"], 2),
+    (&["name-services", "local-hosts"], 2),
+    (&["name-services", "name-mappings"], 3),
+];
+
+/// Replaces uuids, numeric ids and the keys of name-keyed collections in `path` by `{id}`.
+pub fn path_template(path: &str) -> String {
+    let mut template: Vec<&str> = vec![];
+    let mut keys = 0usize;
+    for segment in path.split('/') {
+        let is_uuid = segment.len() == 36 && segment.chars().filter(|c| *c == '-').count() == 4;
+        let is_number = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+        if keys > 0 || is_uuid || is_number {
+            template.push("{id}");
+            keys = keys.saturating_sub(1);
+        } else {
+            template.push(segment);
+            keys = NAME_KEYED_COLLECTIONS
+                .iter()
+                .find(|(collection, _)| template.ends_with(collection))
+                .map_or(0, |(_, keys)| *keys);
+        }
+    }
+    template.join("/")
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use reqwest::Method;
+
+    use super::{path_template, RequestEvent, RequestMetrics, RequestObserver};
+
+    #[test]
+    fn count_requests() {
+        let metrics = RequestMetrics::new();
+        let path = "/api/storage/volumes/028baa66-41bd-11e9-81d5-00a0986138f7/snapshots";
+        for (status, latency) in [(Some(200), 20), (Some(404), 200), (None, 3000)] {
+            metrics.observe(&RequestEvent {
+                method: &Method::GET,
+                path,
+                status,
+                ontap_error_code: None,
+                retries: 1,
+                latency: Duration::from_millis(latency),
+            });
+        }
+
+        let snapshot = metrics.snapshot();
+        let stats = &snapshot[&(
+            "GET".to_string(),
+            "/api/storage/volumes/{id}/snapshots".to_string(),
+        )];
+        assert_eq!(3, stats.count);
+        assert_eq!(2, stats.errors);
+        assert_eq!(3, stats.retries);
+        assert_eq!(1, stats.latency_buckets[1]);
+        assert_eq!(2, stats.latency_buckets[4]);
+        assert_eq!(3, stats.latency_buckets[9]);
+        assert_eq!(
+            "/api/support/ems/events/{id}",
+            path_template("/api/support/ems/events/17")
+        );
+    }
+
+    #[test]
+    fn template_name_keys() {
+        assert_eq!(
+            "/api/cluster/counter/tables/volume/rows/{id}",
+            path_template("/api/cluster/counter/tables/volume/rows/node-01%3Avol1")
+        );
+        assert_eq!(
+            "/api/name-services/unix-groups/{id}/{id}/users",
+            path_template(
+                "/api/name-services/unix-groups/028baa66-41bd-11e9-81d5-00a0986138f7/staff/users"
+            )
+        );
+        assert_eq!(
+            "/api/name-services/name-mappings/{id}/{id}/{id}",
+            path_template(
+                "/api/name-services/name-mappings/028baa66-41bd-11e9-81d5-00a0986138f7/win-unix/1"
+            )
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Embeddable listener for EMS events pushed by a `rest_api` EMS destination.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), rontap::webhook::WebhookError> {
+//! use rontap::webhook::{listen, WebhookConfig};
+//! use tokio_stream::StreamExt;
+//!
+//! let mut events = listen(WebhookConfig {
+//!     shared_secret: Some("s3cr3t".into()),
+//!     ..WebhookConfig::new("0.0.0.0:8443".parse().unwrap())
+//! })
+//! .await?;
+//! while let Some(event) = events.next().await {
+//!     println!("{} {}", event.node.name, event.message.name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The destination configured on the cluster has to carry the secret in the URL, e.g.
+//! `https://listener.example.com:8443/?secret=s3cr3t`, as ONTAP cannot add custom headers.
+//! Reserved characters in the secret have to be percent-encoded.
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use hyper::{
+    body::HttpBody,
+    header::{AUTHORIZATION, CONTENT_LENGTH},
+    server::conn::Http,
+    service::service_fn,
+    Body, Method, Request, Response, StatusCode,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::{net::TcpListener, sync::mpsc, task::JoinHandle};
+use tokio_rustls::{
+    rustls::{self, Certificate, PrivateKey, ServerConfig},
+    TlsAcceptor,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, warn};
+
+use crate::models::ems::EmsEvent;
+
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("TLS error")]
+    Tls(#[from] rustls::Error),
+    #[error("No private key found in PEM data")]
+    MissingPrivateKey,
+}
+
+pub struct WebhookConfig {
+    pub addr: SocketAddr,
+    /// Expected either as `?secret=` query parameter or as `Authorization: Bearer` header.
+    pub shared_secret: Option<String>,
+    pub tls: Option<WebhookTls>,
+    /// Number of events buffered before the listener applies back pressure to ONTAP.
+    pub buffer: usize,
+    /// Larger request bodies are rejected with `413 Payload Too Large`.
+    pub max_body_size: usize,
+}
+
+impl WebhookConfig {
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            shared_secret: None,
+            tls: None,
+            buffer: 1024,
+            max_body_size: 1024 * 1024,
+        }
+    }
+}
+
+pub struct WebhookTls {
+    pub cert_chain_pem: Vec<u8>,
+    pub private_key_pem: Vec<u8>,
+}
+
+impl WebhookTls {
+    fn acceptor(&self) -> Result<TlsAcceptor, WebhookError> {
+        let certs = rustls_pemfile::certs(&mut self.cert_chain_pem.as_slice())?
+            .into_iter()
+            .map(Certificate)
+            .collect();
+        let key = rustls_pemfile::read_all(&mut self.private_key_pem.as_slice())?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+                _ => None,
+            })
+            .ok_or(WebhookError::MissingPrivateKey)?;
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+/// Stream of events received by the listener. Dropping it shuts the listener down.
+pub struct EmsEventStream {
+    local_addr: SocketAddr,
+    events: ReceiverStream<EmsEvent>,
+    server: JoinHandle<()>,
+}
+
+impl EmsEventStream {
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Stream for EmsEventStream {
+    type Item = EmsEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
+
+impl Drop for EmsEventStream {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Binds the listener and serves it on the current tokio runtime.
+pub async fn listen(config: WebhookConfig) -> Result<EmsEventStream, WebhookError> {
+    let acceptor = config.tls.as_ref().map(|t| t.acceptor()).transpose()?;
+    let listener = TcpListener::bind(config.addr).await?;
+    let local_addr = listener.local_addr()?;
+    let secret = config.shared_secret.map(Arc::new);
+    let max_body_size = config.max_body_size;
+    let (tx, rx) = mpsc::channel(config.buffer);
+
+    let server = tokio::spawn(async move {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("failed to accept EMS webhook connection: {e}");
+                    // accept errors like EMFILE persist until a connection is closed, back off
+                    // instead of spinning on them
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            };
+            let acceptor = acceptor.clone();
+            let tx = tx.clone();
+            let secret = secret.clone();
+            tokio::spawn(async move {
+                let service =
+                    service_fn(move |req| handle(req, tx.clone(), secret.clone(), max_body_size));
+                let result = match acceptor {
+                    Some(acceptor) => match acceptor.accept(stream).await {
+                        Ok(stream) => Http::new().serve_connection(stream, service).await,
+                        Err(e) => {
+                            warn!("TLS handshake with {peer} failed: {e}");
+                            return;
+                        }
+                    },
+                    None => Http::new().serve_connection(stream, service).await,
+                };
+                if let Err(e) = result {
+                    debug!("EMS webhook connection from {peer} failed: {e}");
+                }
+            });
+        }
+    });
+
+    Ok(EmsEventStream {
+        local_addr,
+        events: ReceiverStream::new(rx),
+        server,
+    })
+}
+
+async fn handle(
+    req: Request<Body>,
+    tx: mpsc::Sender<EmsEvent>,
+    secret: Option<Arc<String>>,
+    max_body_size: usize,
+) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::POST {
+        return Ok(status(StatusCode::METHOD_NOT_ALLOWED));
+    }
+    if let Some(secret) = secret {
+        if !is_authorized(&req, &secret) {
+            return Ok(status(StatusCode::UNAUTHORIZED));
+        }
+    }
+    let body = match read_body(req, max_body_size).await {
+        Ok(Some(body)) => body,
+        Ok(None) => return Ok(status(StatusCode::PAYLOAD_TOO_LARGE)),
+        Err(_) => return Ok(status(StatusCode::BAD_REQUEST)),
+    };
+    let events = match parse_payload(&body) {
+        Ok(events) => events,
+        Err(e) => {
+            debug!("invalid EMS webhook payload: {e}");
+            return Ok(status(StatusCode::BAD_REQUEST));
+        }
+    };
+    for event in events {
+        if tx.send(event).await.is_err() {
+            return Ok(status(StatusCode::SERVICE_UNAVAILABLE));
+        }
+    }
+    Ok(status(StatusCode::OK))
+}
+
+fn status(code: StatusCode) -> Response<Body> {
+    let mut res = Response::new(Body::empty());
+    *res.status_mut() = code;
+    res
+}
+
+/// Reads the body, `None` when it is larger than `limit` bytes.
+async fn read_body(req: Request<Body>, limit: usize) -> Result<Option<Vec<u8>>, hyper::Error> {
+    let announced = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if announced.is_some_and(|length| length > limit as u64) {
+        return Ok(None);
+    }
+    let mut body = req.into_body();
+    let mut bytes = vec![];
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > limit {
+            return Ok(None);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(Some(bytes))
+}
+
+fn is_authorized(req: &Request<Body>, secret: &str) -> bool {
+    let from_header = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_string);
+    // parsed through `Url` to percent-decode the value
+    let from_query = req.uri().query().and_then(|q| {
+        Url::parse(&format!("http://localhost/?{q}"))
+            .ok()?
+            .query_pairs()
+            .find(|(k, _)| k == "secret")
+            .map(|(_, v)| v.into_owned())
+    });
+    from_header
+        .into_iter()
+        .chain(from_query)
+        .any(|candidate| constant_time_eq(candidate.as_bytes(), secret.as_bytes()))
+}
+
+/// Compares without returning early on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(a.len() ^ b.len(), |acc, (x, y)| acc | usize::from(x ^ y));
+    diff == 0
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Payload {
+    Records { records: Vec<EmsEvent> },
+    Single(EmsEvent),
+}
+
+/// Parses a webhook body, either a single event or a `records` list as returned by
+/// `/support/ems/events`.
+pub fn parse_payload(body: &[u8]) -> Result<Vec<EmsEvent>, serde_json::Error> {
+    Ok(match serde_json::from_slice(body)? {
+        Payload::Records { records } => records,
+        Payload::Single(event) => vec![event],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use tokio_stream::StreamExt;
+
+    use super::{listen, parse_payload, WebhookConfig};
+
+    const EVENT: &str = r#"{
+        "index": 7,
+        "time": "2023-01-10T10:00:00+01:00",
+        "node": { "name": "node-01" },
+        "message": { "name": "callhome.battery.low", "severity": "alert" },
+        "log_message": "callhome.battery.low: Call home for BATTERY_LOW"
+    }"#;
+
+    #[test]
+    fn parse_single_and_records() {
+        assert_eq!(1, parse_payload(EVENT.as_bytes()).unwrap().len());
+        let records = format!(r#"{{ "records": [{EVENT}, {EVENT}] }}"#);
+        assert_eq!(2, parse_payload(records.as_bytes()).unwrap().len());
+        assert!(parse_payload(b"<xml/>").is_err());
+    }
+
+    #[tokio::test]
+    async fn receive_event() {
+        let mut events = listen(WebhookConfig {
+            shared_secret: Some("s3&cr=3t".into()),
+            max_body_size: 1024,
+            ..WebhookConfig::new("127.0.0.1:0".parse().unwrap())
+        })
+        .await
+        .unwrap();
+        let url = format!("http://{}/", events.local_addr());
+        let client = reqwest::Client::new();
+
+        let res = client.post(&url).body(EVENT).send().await.unwrap();
+        assert_eq!(401, res.status().as_u16());
+
+        let authorized = format!("{url}?secret=s3%26cr%3D3t");
+        let res = client
+            .post(&authorized)
+            .body(vec![b' '; 2048])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(413, res.status().as_u16());
+
+        let res = client.post(&authorized).body(EVENT).send().await.unwrap();
+        assert_eq!(200, res.status().as_u16());
+
+        let event = events.next().await.unwrap();
+        assert_eq!(7, event.index);
+        assert_eq!("callhome.battery.low", event.message.name);
+    }
+}